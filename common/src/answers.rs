@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::{bail, line_file, Context, Day, Error, Part, Result, Year};
+
+/// Expected answers of puzzles, for my inputs and the ones of other users.
+///
+/// It's stored as a text file with one answer per line:
+/// ```text
+/// # year day part input answer
+/// 2023 01 1 mine 54990
+/// 2023 01 2 alice 54473
+/// 2022 10 2 mine RUAKHBEK
+/// ```
+/// where "input" is [`MINE`] or the name of another user,
+/// and newlines/backslashes of the answer are escaped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers(BTreeMap<(Year, Day, Part, String), String>);
+
+/// The name of my own inputs in the answers file.
+pub const MINE: &str = "mine";
+
+impl Answers {
+    /// Read the answers file, it's empty if the file does not exist.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        line_file::load(path, "answers")
+    }
+
+    /// Write the answers file, comments are not preserved.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        line_file::save(self, path)
+    }
+
+    #[must_use]
+    pub fn get(&self, year: Year, day: Day, part: Part, input: &str) -> Option<&str> {
+        self.0
+            .get(&(year, day, part, input.to_owned()))
+            .map(String::as_str)
+    }
+
+    /// Insert an answer, and return the previous one if any.
+    pub fn insert(
+        &mut self,
+        year: Year,
+        day: Day,
+        part: Part,
+        input: &str,
+        answer: String,
+    ) -> Result<Option<String>> {
+        if input.is_empty() || input.contains(char::is_whitespace) {
+            bail!("Invalid input name: {:?}", input);
+        }
+        Ok(self.0.insert((year, day, part, input.to_owned()), answer))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Year, Day, Part, &str, &str)> {
+        self.0.iter().map(|((year, day, part, input), answer)| {
+            (*year, *day, *part, input.as_str(), answer.as_str())
+        })
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromStr for Answers {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut answers = Self::default();
+        for mut record in line_file::records(s, 5) {
+            let year = record.field()?.parse()?;
+            let day = record.field()?.parse()?;
+            let part = record.field()?.parse()?;
+            let input = record.field()?;
+            let line_nb = record.line_nb;
+            let answer = unescape(record.field()?).with_context(|| format!("Line {line_nb}"))?;
+            if answers.insert(year, day, part, input, answer)?.is_some() {
+                bail!("Line {}: duplicated answer", line_nb);
+            }
+        }
+        Ok(answers)
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# year day part input answer")?;
+        for (year, day, part, input, answer) in self.iter() {
+            writeln!(
+                f,
+                "{} {:0>2} {} {} {}",
+                i32::from(year),
+                u8::from(day),
+                part.value(1, 2),
+                input,
+                escape(answer),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> Result<String> {
+    let mut answer = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            match chars.next() {
+                Some('\\') => answer.push('\\'),
+                Some('n') => answer.push('\n'),
+                other => bail!("Invalid escape after a backslash: {:?}", other),
+            }
+        } else {
+            answer.push(ch);
+        }
+    }
+    Ok(answer)
+}
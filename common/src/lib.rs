@@ -1,25 +1,30 @@
 /// The common prelude imports:
 /// - the `Part` enum and its variants `Part1` and `Part2` ;
+/// - the `Answer` enum for solvers that do not simply return a number ;
 /// - re-export most of `anyhow`: `bail`, `ensure`, `format_err`, `Context`, `Error`, `Result`
-/// but not its function `Ok` (available outside the prelude) to be able to match against result variants.
+///   but not its function `Ok` (available outside the prelude) to be able to match against result variants.
 /// - `HashMap` and `HashSet` from the `rustc-hash` crate both extended with 2 methods
-/// (`new` and `with_capacity`) to be a nearly drop-in replacement of the ones from `std::collections`
-/// (but it does not implement `From<[(K, V); N]>`).
+///   (`new` and `with_capacity`) to be a nearly drop-in replacement of the ones from `std::collections`
+///   (but it does not implement `From<[(K, V); N]>`).
 ///
 /// Then one can do `use common::prelude::*` in a solver and start get things done without looking back.
 pub mod prelude {
     pub use crate::{bail, ensure, format_err, Context, Error, Result};
-    pub use crate::{Part, Part1, Part2};
+    pub use crate::{Answer, Part, Part1, Part2};
     // My solvers do not need `Day`, `Year`.
     pub use crate::hash::prelude::*;
 }
 
+use std::fmt;
 use std::str::FromStr;
 
 pub use anyhow::{bail, ensure, format_err, Context, Error, Ok, Result};
 
 pub use self::{Day::*, Part::*, Year::*};
 
+pub mod answers;
+pub mod line_file;
+
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Year {
@@ -241,6 +246,105 @@ impl Part {
     }
 }
 
+/// What a solver gives for a part of a puzzle.
+///
+/// Displayed as it would be submitted to the website, except letters that are drawn.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    /// Most puzzles want a number.
+    Int(i128),
+    /// Some want a word, a password, a list...
+    Text(String),
+    /// Some want capital letters drawn by lit pixels, row by row, that must be read.
+    Letters(Vec<Vec<bool>>),
+    /// There is no puzzle on day 25 part 2, only the last star to collect.
+    NoPart2,
+}
+
+impl Answer {
+    /// Characters of lit and dark pixels when letters are displayed.
+    pub const LIT: char = '█';
+    pub const DARK: char = '░';
+
+    #[must_use]
+    #[inline]
+    pub const fn is_no_part2(&self) -> bool {
+        matches!(self, Self::NoPart2)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(n) => n.fmt(f),
+            Self::Text(s) => s.fmt(f),
+            Self::Letters(rows) => rows.iter().enumerate().try_for_each(|(idx, row)| {
+                if idx != 0 {
+                    writeln!(f)?;
+                }
+                row.iter()
+                    .try_for_each(|&lit| write!(f, "{}", if lit { Self::LIT } else { Self::DARK }))
+            }),
+            Self::NoPart2 => f.write_str("No part 2"),
+        }
+    }
+}
+
+/// Compare what would be displayed, mostly useful in tests.
+impl PartialEq<&str> for Answer {
+    #[allow(clippy::cmp_owned)] // Answers are small.
+    fn eq(&self, other: &&str) -> bool {
+        match self {
+            Self::Text(s) => s == other,
+            _ => self.to_string() == *other,
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($int:ty)*) => {
+        $(
+            impl From<$int> for Answer {
+                #[inline]
+                fn from(value: $int) -> Self {
+                    Self::Int(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(u8 u16 u32 u64 i8 i16 i32 i64 i128);
+
+impl From<usize> for Answer {
+    #[inline]
+    #[allow(clippy::cast_possible_wrap)] // `usize` has at most 64 bits.
+    fn from(value: usize) -> Self {
+        Self::Int(value as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    #[inline]
+    fn from(value: isize) -> Self {
+        Self::Int(value as i128)
+    }
+}
+
+impl From<String> for Answer {
+    #[inline]
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    #[inline]
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_owned())
+    }
+}
+
 pub mod hash {
     #![allow(clippy::default_trait_access, clippy::implicit_hasher)]
 
@@ -269,7 +373,6 @@ pub mod hash {
     }
 
     impl<K, V> FxHasherHack for HashMap<K, V> {
-        #[must_use]
         #[inline]
         fn with_capacity(capacity: usize) -> Self {
             Self::with_capacity_and_hasher(capacity, Default::default())
@@ -277,7 +380,6 @@ pub mod hash {
     }
 
     impl<T> FxHasherHack for HashSet<T> {
-        #[must_use]
         #[inline]
         fn with_capacity(capacity: usize) -> Self {
             Self::with_capacity_and_hasher(capacity, Default::default())
@@ -0,0 +1,67 @@
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::str::{FromStr, SplitN};
+
+use crate::{Context, Error, Result};
+
+/// Read a text file of records, its content is the default value if the file does not exist.
+///
+/// `what` names the file in error messages.
+pub fn load<T, P>(path: P, what: &str) -> Result<T>
+where
+    T: Default + FromStr<Err = Error>,
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    match fs::read_to_string(path) {
+        Ok(text) => text
+            .parse()
+            .with_context(|| format!("Invalid {what} file {}", path.display())),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(T::default()),
+        Err(err) => Err(err).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// Write a text file of records.
+pub fn save<T, P>(value: &T, path: P) -> Result<()>
+where
+    T: fmt::Display,
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    fs::write(path, value.to_string())
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// The records of a text file, one per line with `nb_fields` fields separated by spaces,
+/// the last one being the rest of the line. Empty lines and `#` comments are skipped.
+pub fn records(text: &str, nb_fields: usize) -> impl Iterator<Item = Record<'_>> {
+    text.lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim_start()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(move |(line_nb, line)| Record {
+            line_nb,
+            fields: line.splitn(nb_fields, ' '),
+        })
+}
+
+/// The fields of a line of a text file of records.
+#[derive(Debug, Clone)]
+pub struct Record<'a> {
+    /// One-based, for error messages.
+    pub line_nb: usize,
+    fields: SplitN<'a, char>,
+}
+
+impl<'a> Record<'a> {
+    /// The next field, an error if the line is too short.
+    pub fn field(&mut self) -> Result<&'a str> {
+        let line_nb = self.line_nb;
+        self.fields
+            .next()
+            .with_context(|| format!("Line {line_nb}: too short"))
+    }
+}
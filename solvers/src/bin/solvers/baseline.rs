@@ -0,0 +1,159 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use common::{bail, Context, Day, Part, Result, Year};
+
+/// Median timings of solvers on my big inputs, along with where they come from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    /// Git commit of the workspace, if known.
+    pub commit: Option<String>,
+    /// "debug" or "release" (any optimized profile).
+    pub profile: String,
+    pub timings: BTreeMap<(Year, Day, Part), Duration>,
+}
+
+impl Snapshot {
+    /// Timings of the current build.
+    pub fn new(timings: BTreeMap<(Year, Day, Part), Duration>) -> Self {
+        let commit = Command::new("git")
+            .args(["rev-parse", "--short", "HEAD"])
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|commit| commit.trim().to_owned());
+        let profile = if cfg!(debug_assertions) {
+            "debug"
+        } else {
+            "release"
+        };
+        Self {
+            commit,
+            profile: profile.to_owned(),
+            timings,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let value: Value = serde_json::from_str(&text)?;
+        let commit = value["commit"].as_str().map(str::to_owned);
+        let profile = value["profile"].as_str().context("No profile")?.to_owned();
+        let mut timings = BTreeMap::new();
+        for timing in value["timings"].as_array().context("No timings")? {
+            let number = |key: &str| timing[key].as_u64().with_context(|| format!("No {key}"));
+            let year: Year = number("year")?.to_string().parse()?;
+            let day: Day = number("day")?.to_string().parse()?;
+            let part: Part = number("part")?.to_string().parse()?;
+            let t = Duration::from_nanos(number("nanoseconds")?);
+            if timings.insert((year, day, part), t).is_some() {
+                bail!("Duplicated timing: {:?} {:?} {:?}", year, day, part);
+            }
+        }
+        Ok(Self {
+            commit,
+            profile,
+            timings,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let timings: Vec<_> = self
+            .timings
+            .iter()
+            .map(|((year, day, part), t)| {
+                json!({
+                    "year": i32::from(*year),
+                    "day": u8::from(*day),
+                    "part": part.value(1, 2),
+                    "nanoseconds": u64::try_from(t.as_nanos()).unwrap_or(u64::MAX),
+                })
+            })
+            .collect();
+        let value = json!({
+            "commit": self.commit,
+            "profile": self.profile,
+            "timings": timings,
+        });
+        fs::write(path, serde_json::to_string_pretty(&value)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Print how timings changed since the baseline, the biggest slowdowns first,
+    /// and return the number of solvers slower than the threshold (in percent).
+    ///
+    /// It goes to stderr when stdout has structured output.
+    pub fn print_diff(&self, baseline: &Self, threshold: f64, to_stderr: bool) -> usize {
+        let print = |line: String| {
+            if to_stderr {
+                eprintln!("{line}");
+            } else {
+                println!("{line}");
+            }
+        };
+        let mut diffs: Vec<_> = self
+            .timings
+            .iter()
+            .filter_map(|(key, &new)| {
+                let old = *baseline.timings.get(key)?;
+                let change = 100.0 * (new.as_secs_f64() / old.as_secs_f64() - 1.0);
+                Some((key, old, new, change))
+            })
+            .collect();
+        diffs.sort_by(|a, b| b.3.total_cmp(&a.3));
+        print(format!(
+            "========== Diff with baseline ({}, {}) ==========",
+            baseline.commit.as_deref().unwrap_or("unknown commit"),
+            baseline.profile,
+        ));
+        if baseline.profile != self.profile {
+            print(format!(
+                "Warning: comparing {} timings to {} ones",
+                self.profile, baseline.profile
+            ));
+        }
+        let mut slower = 0;
+        for ((year, day, part), old, new, change) in &diffs {
+            let flag = if *change > threshold {
+                slower += 1;
+                "  SLOWER"
+            } else {
+                ""
+            };
+            print(format!(
+                "{year:?} {day:?} {part:?}: {old:?} -> {new:?} ({change:+.1}%){flag}"
+            ));
+        }
+        let (old, new): (Duration, Duration) = diffs
+            .iter()
+            .fold(Default::default(), |(olds, news), (_, old, new, _)| {
+                (olds + *old, news + *new)
+            });
+        print(format!("{} solvers: {old:?} -> {new:?}", diffs.len()));
+        let added = self
+            .timings
+            .keys()
+            .filter(|key| !baseline.timings.contains_key(key))
+            .count();
+        let removed = baseline
+            .timings
+            .keys()
+            .filter(|key| !self.timings.contains_key(key))
+            .count();
+        if added != 0 || removed != 0 {
+            print(format!(
+                "{added} not in the baseline, {removed} no longer measured"
+            ));
+        }
+        print(format!("{slower} slower than {threshold}%\n"));
+        slower
+    }
+}
@@ -0,0 +1,88 @@
+use std::path::Path;
+
+use common::answers::{Answers, MINE};
+use common::{bail, Answer, Day, Part, Result, Year};
+use solvers::{aoc, InputProvider};
+
+/// The result of a solver compared to the expected answer.
+#[derive(Debug)]
+enum Status {
+    Pass,
+    Fail { expected: String, answer: Answer },
+    Error(String),
+    Missing,
+}
+
+/// Run solvers on my inputs and other users' inputs,
+/// and compare their answers to the ones stored in the answers file.
+///
+/// Fail if any answer is wrong, if a solver returns an error, or if nothing could be checked.
+pub fn run_check(
+    provider: &InputProvider,
+    answers_path: &Path,
+    years: &[Year],
+    days: &[Day],
+    parts: &[Part],
+) -> Result<()> {
+    let answers = Answers::load(answers_path)?;
+    let usernames = provider.other_usernames();
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for &year in years {
+        for &day in days {
+            let Ok((solver, _)) = aoc(year, day) else {
+                continue;
+            };
+            let inputs = std::iter::once(MINE).chain(usernames.iter().map(String::as_str));
+            for name in inputs {
+                let input = if name == MINE {
+                    provider.big_input(year, day)?
+                } else {
+                    provider.other_input(name, year, day)?
+                };
+                let Some(input) = input else {
+                    continue;
+                };
+                for &part in parts {
+                    let status = match answers.get(year, day, part, name) {
+                        None => Status::Missing,
+                        Some(expected) => match solver.solve(part, &input) {
+                            Ok(answer) if answer == expected => Status::Pass,
+                            Ok(answer) => Status::Fail {
+                                expected: expected.to_owned(),
+                                answer,
+                            },
+                            Err(err) => Status::Error(format!("{err:#}")),
+                        },
+                    };
+                    let puzzle = format!("{year:?} {day:?} {part:?} {name}");
+                    match status {
+                        Status::Pass => {
+                            passed += 1;
+                            println!("{puzzle}: pass");
+                        }
+                        Status::Fail { expected, answer } => {
+                            failed += 1;
+                            println!("{puzzle}: FAIL\n  expected: {expected}\n  got: {answer}");
+                        }
+                        Status::Error(err) => {
+                            failed += 1;
+                            println!("{puzzle}: FAIL\n  error: {err}");
+                        }
+                        Status::Missing => {
+                            missing += 1;
+                            println!("{puzzle}: missing answer");
+                        }
+                    }
+                }
+            }
+        }
+    }
+    println!("\n{passed} passed, {failed} failed, {missing} missing answers");
+    if failed != 0 {
+        bail!("{} wrong answers or errors", failed);
+    }
+    if passed == 0 {
+        bail!("Nothing was checked: no input with a known answer");
+    }
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use std::any::Any;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use common::{Error, Result};
+
+/// Why a solver was stopped before giving an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Abort {
+    Panic(String),
+    Timeout(Duration),
+}
+
+impl fmt::Display for Abort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Panic(msg) => write!(f, "panicked: {msg}"),
+            Self::Timeout(t) => write!(f, "timed out after {t:?}"),
+        }
+    }
+}
+
+impl std::error::Error for Abort {}
+
+/// How a solver ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Error,
+    Panic,
+    Timeout,
+}
+
+impl Status {
+    pub fn of<T>(outcome: &Result<T>) -> Self {
+        match outcome {
+            Ok(_) => Self::Ok,
+            Err(err) => match err.downcast_ref() {
+                Some(Abort::Panic(_)) => Self::Panic,
+                Some(Abort::Timeout(_)) => Self::Timeout,
+                None => Self::Error,
+            },
+        }
+    }
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Error => "error",
+            Self::Panic => "panic",
+            Self::Timeout => "timeout",
+        }
+    }
+}
+
+/// Run `job` on its own thread so that a panic or a timeout becomes an error.
+///
+/// A thread that times out can not be killed, it's left running in the background.
+/// Panics are only caught when the profile unwinds (not the "final" one).
+pub fn isolate<T, F>(timeout: Option<Duration>, job: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new().spawn(move || {
+        let res = panic::catch_unwind(AssertUnwindSafe(job));
+        // The receiver is gone on timeout, then nobody cares about the result.
+        let _ = sender.send(res);
+    })?;
+    let res = match timeout {
+        Some(t) => receiver.recv_timeout(t).map_err(|err| match err {
+            RecvTimeoutError::Timeout => Abort::Timeout(t),
+            RecvTimeoutError::Disconnected => Abort::Panic("unknown".to_owned()),
+        }),
+        None => receiver
+            .recv()
+            .map_err(|_| Abort::Panic("unknown".to_owned())),
+    }?;
+    res.map_err(|payload| Error::new(Abort::Panic(panic_message(payload.as_ref()))))?
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|msg| (*msg).to_owned())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown".to_owned())
+}
@@ -0,0 +1,555 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use common::{bail, ensure, format_err, Answer, Context, Day, Part, Part1, Part2, Result, Year};
+use solvers::{aoc, InputProvider};
+
+mod baseline;
+mod check;
+mod isolate;
+mod output;
+mod pool;
+mod stats;
+
+use baseline::Snapshot;
+use check::run_check;
+use isolate::{isolate, Status};
+use output::{Format, InputId, Record};
+use stats::{Repetition, Timings};
+
+/// How the runner runs solvers and reports results.
+#[derive(Debug, Clone, Copy, Default)]
+struct Settings {
+    format: Format,
+    repetition: Repetition,
+    /// Number of puzzles run concurrently on big inputs.
+    jobs: usize,
+    /// Time limit of each run of each solver on each part.
+    timeout: Option<Duration>,
+}
+
+/// Measure a solver, each run on its own thread: panics and timeouts become errors.
+fn measure_isolated(
+    year: Year,
+    day: Day,
+    part: Part,
+    input: Arc<str>,
+    settings: Settings,
+) -> Result<(Answer, Timings)> {
+    let Settings {
+        repetition,
+        timeout,
+        ..
+    } = settings;
+    repetition.measure(|| {
+        let input = Arc::clone(&input);
+        isolate(timeout, move || {
+            let (solver, _) = aoc(year, day)?;
+            solver.timed_solve(part, &input)
+        })
+    })
+}
+
+/// Which inputs to run a solver on.
+#[derive(Debug, Default)]
+enum InputChoice {
+    /// Examples and my big input.
+    #[default]
+    All,
+    /// One of the examples or my big input.
+    Index(usize),
+    /// Any file.
+    File(PathBuf),
+    /// The standard input.
+    Stdin,
+}
+
+impl InputChoice {
+    /// Identifiers and contents of the chosen inputs.
+    fn load(
+        &self,
+        provider: &InputProvider,
+        year: Year,
+        day: Day,
+        examples: &[&str],
+    ) -> Result<Vec<(InputId, String)>> {
+        Ok(match self {
+            Self::All | Self::Index(_) => {
+                // Examples then my big input (if available).
+                let big_input = provider.big_input(year, day)?;
+                if big_input.is_none() {
+                    eprintln!(
+                        "No input at {}",
+                        provider.big_input_path(year, day).display()
+                    );
+                }
+                let mut inputs: Vec<_> = examples
+                    .iter()
+                    .map(|&example| example.to_owned())
+                    .chain(big_input)
+                    .enumerate()
+                    .map(|(idx, input)| (InputId::Index(idx), input))
+                    .collect();
+                // Given index or all.
+                if let Self::Index(idx) = *self {
+                    let nb = inputs.len();
+                    ensure!(idx < nb, "No input #{idx}, there are only {nb} inputs!");
+                    inputs = vec![inputs.swap_remove(idx)];
+                }
+                inputs
+            }
+            Self::File(path) => {
+                let input = fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                vec![(InputId::File(path.clone()), input)]
+            }
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .context("Failed to read the standard input")?;
+                vec![(InputId::Stdin, input)]
+            }
+        })
+    }
+}
+
+fn run_some_inputs(
+    provider: &InputProvider,
+    year: Year,
+    day: Day,
+    parts: &[Part],
+    choice: &InputChoice,
+    settings: Settings,
+) -> Result<()> {
+    let format = settings.format;
+    let (_, examples) = aoc(year, day)?;
+    let inputs = choice.load(provider, year, day, examples)?;
+    // Run the solver on selected parts and inputs.
+    if format.is_structured() {
+        format.print_header();
+    } else {
+        println!("Advent of Code {year:?} {day:?}...");
+    }
+    for &part in parts {
+        for (id, input) in &inputs {
+            let outcome = if input.is_empty() {
+                Err(format_err!("Empty input: you forgot to fill it?!"))
+            } else {
+                measure_isolated(year, day, part, input.as_str().into(), settings)
+            };
+            if format.is_structured() {
+                let input = id.clone();
+                format.print_record(&Record {
+                    year,
+                    day,
+                    part,
+                    input,
+                    outcome,
+                });
+            } else {
+                println!("\n{part:?} {id}:");
+                let (result, t) = outcome?;
+                println!("[ Done in {t} ]\n{result}");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Both parts of a solver on my big input.
+struct BigRun {
+    day: Day,
+    /// Index of my big input, after the examples.
+    index: usize,
+    /// `None` when my input is missing.
+    outcomes: Option<[Result<(Answer, Timings)>; 2]>,
+}
+
+impl BigRun {
+    /// Time spent in the measured runs of the solvers.
+    fn solver_time(&self) -> Duration {
+        self.outcomes
+            .iter()
+            .flatten()
+            .filter_map(|outcome| outcome.as_ref().ok())
+            .map(|(_, timings)| timings.total())
+            .sum()
+    }
+}
+
+/// `None` when there is no solver for this puzzle.
+fn run_big_input(
+    provider: &InputProvider,
+    year: Year,
+    day: Day,
+    settings: Settings,
+) -> Result<Option<BigRun>> {
+    let Ok((_, examples)) = aoc(year, day) else {
+        return Ok(None);
+    };
+    let outcomes = provider.big_input(year, day)?.map(|big_input| {
+        let big_input: Arc<str> = big_input.into();
+        Part::ALL.map(|part| measure_isolated(year, day, part, Arc::clone(&big_input), settings))
+    });
+    Ok(Some(BigRun {
+        day,
+        index: examples.len(),
+        outcomes,
+    }))
+}
+
+/// Median timings on big inputs and the number of failed solvers (errors, panics, timeouts).
+fn run_big_inputs(
+    provider: &InputProvider,
+    years: &[Year],
+    settings: Settings,
+) -> Result<(Medians, usize)> {
+    let now = Instant::now();
+    let (mut medians, mut failed) = (Medians::new(), 0);
+    let mut solver_time = Duration::ZERO;
+    // Years are reported as soon as they are done.
+    for &year in years {
+        let runs = pool::map_ordered(settings.jobs, &Day::ALL, |&day| {
+            run_big_input(provider, year, day, settings)
+        });
+        let runs = runs
+            .into_iter()
+            .filter_map(Result::transpose)
+            .collect::<Result<Vec<_>>>()?;
+        solver_time += runs.iter().map(BigRun::solver_time).sum::<Duration>();
+        let (year_medians, year_failed) = report_big_runs(provider, year, runs, settings);
+        medians.extend(year_medians);
+        failed += year_failed;
+    }
+    let wall_time = now.elapsed();
+    let summary = format!(
+        "Wall time: {wall_time:?}, summed solver time: {solver_time:?} ({} jobs)",
+        settings.jobs,
+    );
+    if settings.format.is_structured() {
+        eprintln!("{summary}");
+    } else {
+        println!("{summary}");
+    }
+    Ok((medians, failed))
+}
+
+/// Median timings of solvers on my big inputs.
+type Medians = BTreeMap<(Year, Day, Part), Duration>;
+
+fn report_big_runs(
+    provider: &InputProvider,
+    year: Year,
+    runs: Vec<BigRun>,
+    settings: Settings,
+) -> (Medians, usize) {
+    let Settings {
+        format, repetition, ..
+    } = settings;
+    let mut medians = Medians::new();
+    let mut failures = vec![];
+    let mut results: Vec<(Day, Timings, Timings)> = Vec::with_capacity(50);
+    let mut missing = 0;
+    for BigRun {
+        day,
+        index,
+        outcomes,
+        ..
+    } in runs
+    {
+        let Some(outcomes) = outcomes else {
+            missing += 1;
+            continue;
+        };
+        if format.is_structured() {
+            for (part, outcome) in Part::ALL.into_iter().zip(outcomes) {
+                match &outcome {
+                    Ok((_, t)) => {
+                        medians.insert((year, day, part), t.median());
+                    }
+                    Err(err) => {
+                        failures.push((day, part, Status::of(&outcome), format!("{err:#}")))
+                    }
+                }
+                let input = InputId::Index(index);
+                format.print_record(&Record {
+                    year,
+                    day,
+                    part,
+                    input,
+                    outcome,
+                });
+            }
+            continue;
+        }
+        // A failed part excludes the day from the table.
+        match outcomes {
+            [Ok((_, t1)), Ok((_, t2))] => {
+                medians.insert((year, day, Part1), t1.median());
+                medians.insert((year, day, Part2), t2.median());
+                results.push((day, t1, t2));
+            }
+            outcomes => {
+                for (part, outcome) in Part::ALL.into_iter().zip(outcomes) {
+                    if let Err(err) = &outcome {
+                        failures.push((day, part, Status::of(&outcome), format!("{err:#}")));
+                    }
+                }
+            }
+        }
+    }
+    if missing != 0 {
+        eprintln!(
+            "{year:?}: {missing} solvers skipped, no input in {}",
+            provider.dir().display()
+        );
+    }
+    // Medians are more stable than single runs.
+    results.sort_by_key(|(_, t1, t2)| t1.median() + t2.median());
+    if !results.is_empty() {
+        println!("========== {year:?} ==========");
+        if repetition.runs > 1 {
+            println!(
+                "(medians of {} runs after {} warmup runs)",
+                repetition.runs, repetition.warmup
+            );
+        }
+        for (day, t1, t2) in &results {
+            let (m1, m2) = (t1.median(), t2.median());
+            println!("{day:?}: {m1:?} + {m2:?} == {:?}", m1 + m2);
+            if repetition.runs > 1 {
+                println!("    Part1: {t1}\n    Part2: {t2}");
+            }
+        }
+        let t1s: Duration = results.iter().map(|(_, t1, _)| t1.median()).sum();
+        let t2s: Duration = results.iter().map(|(_, _, t2)| t2.median()).sum();
+        println!(
+            "{} days: {t1s:?} + {t2s:?} == {:?}\n",
+            results.len(),
+            t1s + t2s
+        );
+    }
+    if !format.is_structured() && !failures.is_empty() {
+        if results.is_empty() {
+            println!("========== {year:?} ==========");
+        }
+        for (day, part, status, err) in &failures {
+            println!("{day:?} {part:?}: [{}] {err}", status.as_str());
+        }
+        println!();
+    }
+    (medians, failures.len())
+}
+
+const HELP: &str = "\
+USAGE:
+  cargo run [YEAR [DAY [PART [INDEX | -]]]] [OPTIONS]
+
+FLAGS:
+  -h, --help         Prints help information
+  -c, --check        Compare answers on my inputs and other users' inputs to the
+                     answers file, fail on any mismatch or if nothing was
+                     checked (INDEX and --format not allowed)
+
+OPTIONS:
+  -i, --input FILE   Run a puzzle on the given file instead (needs YEAR and DAY)
+  -f, --format FMT   text | json | csv    One record per puzzle part and input,
+                                          with answer, nanoseconds and error
+  -a, --answers FILE Answers file to check against, it must exist
+                     (default: <inputs>/answers.txt)
+  -r, --repeat N     Measure N runs of each solver (default: 1), then report
+                     the median and min/mean/p95/stddev
+  -w, --warmup N     Run each solver N times before measuring it (default: 0)
+  -s, --save-baseline FILE   Save median timings on big inputs (without DAY)
+  -b, --baseline FILE        Compare median timings on big inputs to a saved
+                             baseline (without DAY), fail on slowdowns
+  -t, --threshold PCT        Slowdown tolerated by the comparison (default: 10)
+  -T, --timeout SECS Stop waiting for a solver after SECS seconds on a run,
+                     timeouts and panics are reported instead of aborting
+  -j, --jobs N       Run up to N puzzles concurrently on big inputs (default: 1,
+                     0 for as many as CPUs), results are still in order
+
+ARGS:
+  YEAR    [20]15..   Or run all years only on your big inputs   timings only
+  DAY     1..=25     Or run all days only on your big inputs    timings only
+  PART    1 | 2      Or run all parts on all inputs             answers & timings
+  INDEX   0..        Or run all inputs                          answers & timings
+          -          Or run on the standard input               answers & timings
+
+ENVIRONMENT:
+  AOC_INPUTS         Directory of your big inputs (default: <workspace>/inputs)
+";
+
+#[derive(Debug)]
+struct Args {
+    year: Option<Year>,
+    day: Option<Day>,
+    part: Option<Part>,
+    input: InputChoice,
+    settings: Settings,
+    check: bool,
+    answers: Option<PathBuf>,
+    save_baseline: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+}
+
+impl Args {
+    fn from_env() -> Result<Self> {
+        let mut positional = vec![];
+        let mut input_file = None;
+        let mut settings = Settings {
+            jobs: 1,
+            ..Default::default()
+        };
+        let (mut check, mut answers) = (false, None);
+        let (mut save_baseline, mut baseline, mut threshold) = (None, None, 10.0);
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => {
+                    print!("{HELP}");
+                    std::process::exit(0);
+                }
+                "-i" | "--input" => {
+                    ensure!(input_file.is_none(), "Only one input file expected");
+                    input_file = Some(args.next().context("Missing input filepath")?);
+                }
+                "-c" | "--check" => check = true,
+                "-a" | "--answers" => {
+                    answers = Some(args.next().context("Missing answers filepath")?.into());
+                }
+                "-f" | "--format" => {
+                    settings.format = args.next().context("Missing output format")?.parse()?;
+                }
+                "-r" | "--repeat" => {
+                    settings.repetition.runs = args.next().context("Missing runs")?.parse()?;
+                    ensure!(settings.repetition.runs != 0, "At least one run");
+                }
+                "-w" | "--warmup" => {
+                    settings.repetition.warmup =
+                        args.next().context("Missing warmup runs")?.parse()?;
+                }
+                "-T" | "--timeout" => {
+                    let secs: f64 = args.next().context("Missing timeout")?.parse()?;
+                    settings.timeout = Some(Duration::try_from_secs_f64(secs)?);
+                }
+                "-j" | "--jobs" => {
+                    settings.jobs = match args.next().context("Missing jobs")?.parse()? {
+                        0 => thread::available_parallelism().map_or(1, Into::into),
+                        jobs => jobs,
+                    };
+                }
+                "-s" | "--save-baseline" => {
+                    save_baseline = Some(args.next().context("Missing baseline filepath")?.into());
+                }
+                "-b" | "--baseline" => {
+                    baseline = Some(args.next().context("Missing baseline filepath")?.into());
+                }
+                "-t" | "--threshold" => {
+                    threshold = args.next().context("Missing threshold")?.parse()?;
+                }
+                _ => positional.push(arg),
+            }
+        }
+        ensure!(positional.len() <= 4, "Up to four arguments expected");
+        let input = match (positional.get(3).map(String::as_str), input_file) {
+            (Some(_), Some(_)) => bail!("Either an input index or an input file, not both"),
+            (Some("-"), None) => InputChoice::Stdin,
+            (Some(index), None) => InputChoice::Index(index.parse()?),
+            (None, Some(path)) => InputChoice::File(path.into()),
+            (None, None) => InputChoice::All,
+        };
+        let args = Self {
+            year: positional.first().map(|s| s.parse()).transpose()?,
+            day: positional.get(1).map(|s| s.parse()).transpose()?,
+            part: positional.get(2).map(|s| s.parse()).transpose()?,
+            input,
+            settings,
+            check,
+            answers,
+            save_baseline,
+            baseline,
+            threshold,
+        };
+        ensure!(
+            args.day.is_some() || matches!(args.input, InputChoice::All),
+            "A year and a day are needed to run a solver on a given input"
+        );
+        ensure!(
+            !args.check || matches!(args.input, InputChoice::All),
+            "Check all inputs, not a given one"
+        );
+        ensure!(
+            !args.check || !args.settings.format.is_structured(),
+            "The check only has a text output"
+        );
+        ensure!(
+            args.check || args.answers.is_none(),
+            "An answers file is only used by the check"
+        );
+        ensure!(
+            args.day.is_none() || (args.save_baseline.is_none() && args.baseline.is_none()),
+            "Baselines are about big inputs of all days"
+        );
+        Ok(args)
+    }
+
+    fn run(&self) -> Result<()> {
+        let provider = InputProvider::default();
+        if self.check {
+            // The default answers file can be missing, not a given one.
+            let answers_path = match &self.answers {
+                Some(path) => {
+                    ensure!(path.is_file(), "No answers file at {}", path.display());
+                    path.clone()
+                }
+                None => provider.answers_path(),
+            };
+            let years = self.year.map_or_else(|| Year::ALL.to_vec(), |y| vec![y]);
+            let days = self.day.map_or_else(|| Day::ALL.to_vec(), |d| vec![d]);
+            let parts = self.part.map_or_else(|| Part::ALL.to_vec(), |p| vec![p]);
+            return run_check(&provider, &answers_path, &years, &days, &parts);
+        }
+        // All solvers on big inputs: all years or only the given one.
+        let Some(day) = self.day else {
+            let years = self.year.map_or_else(|| Year::ALL.to_vec(), |y| vec![y]);
+            self.settings.format.print_header();
+            let (medians, failed) = run_big_inputs(&provider, &years, self.settings)?;
+            self.compare_to_baselines(medians)?;
+            ensure!(failed == 0, "{failed} solvers failed");
+            return Ok(());
+        };
+        let year = self.year.context("A year is needed with a day")?;
+        // Given part or both.
+        let parts = self.part.map_or_else(|| Part::ALL.to_vec(), |p| vec![p]);
+        // Some part(s) and some inputs for a given puzzle.
+        run_some_inputs(&provider, year, day, &parts, &self.input, self.settings)
+    }
+
+    fn compare_to_baselines(&self, medians: Medians) -> Result<()> {
+        let snapshot = Snapshot::new(medians);
+        if let Some(path) = &self.save_baseline {
+            snapshot.save(path)?;
+            eprintln!("Baseline saved at {}", path.display());
+        }
+        if let Some(path) = &self.baseline {
+            let baseline = Snapshot::load(path)?;
+            let to_stderr = self.settings.format.is_structured();
+            let slower = snapshot.print_diff(&baseline, self.threshold, to_stderr);
+            ensure!(slower == 0, "{slower} solvers got slower than the baseline");
+        }
+        Ok(())
+    }
+}
+
+fn main() -> Result<()> {
+    Args::from_env()?.run()
+}
@@ -0,0 +1,237 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use common::{bail, Answer, Day, Error, Part, Result, Year};
+
+use crate::isolate::Status;
+use crate::stats::Timings;
+
+/// How the runner reports its results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Human-readable answers and timings.
+    #[default]
+    Text,
+    /// One JSON object per line.
+    Json,
+    /// Comma-separated values, with a header.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "text" => Self::Text,
+            "json" => Self::Json,
+            "csv" => Self::Csv,
+            v => bail!("Failed to parse output format (text json csv): {}", v),
+        })
+    }
+}
+
+/// Where an input comes from, as given on the command line.
+#[derive(Debug, Clone)]
+pub enum InputId {
+    Index(usize),
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputId {
+    /// The command line argument to run the solver on this input again.
+    fn arg(&self) -> String {
+        match self {
+            Self::Index(idx) => idx.to_string(),
+            Self::File(path) => path.display().to_string(),
+            Self::Stdin => "-".to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for InputId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Index(idx) => write!(f, "input #{idx}"),
+            Self::File(path) => write!(f, "input {}", path.display()),
+            Self::Stdin => f.write_str("standard input"),
+        }
+    }
+}
+
+/// The outcome of a solver on one part of one input.
+#[derive(Debug)]
+pub struct Record {
+    pub year: Year,
+    pub day: Day,
+    pub part: Part,
+    pub input: InputId,
+    pub outcome: Result<(Answer, Timings)>,
+}
+
+impl Record {
+    fn answer(&self) -> Option<String> {
+        self.outcome
+            .as_ref()
+            .ok()
+            .map(|(answer, _)| answer.to_string())
+    }
+
+    fn timings(&self) -> Option<&Timings> {
+        self.outcome.as_ref().ok().map(|(_, timings)| timings)
+    }
+
+    /// The median duration of the measured runs.
+    fn nanoseconds(&self) -> Option<u64> {
+        self.timings().map(|t| nanoseconds(t.median()))
+    }
+
+    fn error(&self) -> Option<String> {
+        self.outcome.as_ref().err().map(|err| format!("{err:#}"))
+    }
+}
+
+impl Format {
+    /// The text format is handled by the runner itself, others only print records.
+    pub const fn is_structured(self) -> bool {
+        !matches!(self, Self::Text)
+    }
+
+    /// What comes before any record.
+    const fn header(self) -> Option<&'static str> {
+        match self {
+            Self::Csv => Some(
+                "year,day,part,input,answer,nanoseconds,error,runs,min_ns,mean_ns,p95_ns,stddev_ns,status",
+            ),
+            Self::Text | Self::Json => None,
+        }
+    }
+
+    /// Print what comes before any record.
+    pub fn print_header(self) {
+        if let Some(header) = self.header() {
+            println!("{header}");
+        }
+    }
+
+    /// Print one record on its own line (or more for CSV answers on multiple lines).
+    pub fn print_record(self, record: &Record) {
+        if let Some(line) = self.record(record) {
+            println!("{line}");
+        }
+    }
+
+    fn record(self, record: &Record) -> Option<String> {
+        Some(match self {
+            Self::Text => return None,
+            Self::Json => {
+                let value = serde_json::json!({
+                    "year": i32::from(record.year),
+                    "day": u8::from(record.day),
+                    "part": record.part.value(1, 2),
+                    "input": record.input.arg(),
+                    "answer": record.answer(),
+                    "nanoseconds": record.nanoseconds(),
+                    "runs": record.timings().map(Timings::runs),
+                    "min_ns": record.timings().map(|t| nanoseconds(t.min())),
+                    "mean_ns": record.timings().map(|t| nanoseconds(t.mean())),
+                    "p95_ns": record.timings().map(|t| nanoseconds(t.p95())),
+                    "stddev_ns": record.timings().map(|t| nanoseconds(t.stddev())),
+                    "error": record.error(),
+                    "status": Status::of(&record.outcome).as_str(),
+                });
+                value.to_string()
+            }
+            Self::Csv => {
+                let timings = record.timings().map_or_else(
+                    || ",,,,".to_owned(),
+                    |t| {
+                        format!(
+                            "{},{},{},{},{}",
+                            t.runs(),
+                            nanoseconds(t.min()),
+                            nanoseconds(t.mean()),
+                            nanoseconds(t.p95()),
+                            nanoseconds(t.stddev()),
+                        )
+                    },
+                );
+                format!(
+                    "{},{},{},{},{},{},{},{},{}",
+                    i32::from(record.year),
+                    u8::from(record.day),
+                    record.part.value(1, 2),
+                    csv_field(&record.input.arg()),
+                    record
+                        .answer()
+                        .map(|answer| csv_field(&answer))
+                        .unwrap_or_default(),
+                    record
+                        .nanoseconds()
+                        .map(|ns| ns.to_string())
+                        .unwrap_or_default(),
+                    record.error().as_deref().map(csv_field).unwrap_or_default(),
+                    timings,
+                    Status::of(&record.outcome).as_str(),
+                )
+            }
+        })
+    }
+}
+
+fn nanoseconds(t: Duration) -> u64 {
+    t.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+/// Quote the field if needed, as in RFC 4180.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{format_err, Day1, Part1, Year2023};
+
+    use super::*;
+
+    /// Number of fields of a CSV line, quoted fields included.
+    fn nb_fields(line: &str) -> usize {
+        let mut quoted = false;
+        1 + line
+            .chars()
+            .filter(|&ch| {
+                if ch == '"' {
+                    quoted = !quoted;
+                }
+                ch == ',' && !quoted
+            })
+            .count()
+    }
+
+    #[test]
+    fn csv_header_matches_records() {
+        let header = Format::Csv.header().unwrap_or_default();
+        let record = |outcome| Record {
+            year: Year2023,
+            day: Day1,
+            part: Part1,
+            input: InputId::Index(0),
+            outcome,
+        };
+        let timings = Timings::new(vec![Duration::from_millis(1); 3]);
+        for record in [
+            record(Ok((Answer::from("a,\"b\"".to_owned()), timings))),
+            record(Err(format_err!("failed, badly"))),
+        ] {
+            let line = Format::Csv.record(&record).unwrap_or_default();
+            assert_eq!(nb_fields(&line), nb_fields(header), "{line}");
+        }
+    }
+}
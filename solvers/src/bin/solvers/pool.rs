@@ -0,0 +1,46 @@
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Apply `f` to all items on up to `jobs` threads.
+///
+/// Items are taken in order by the first available thread,
+/// and results are given back in the same order as the items.
+pub fn map_ordered<I, T, F>(jobs: usize, items: &[I], f: F) -> Vec<T>
+where
+    I: Sync,
+    T: Send,
+    F: Fn(&I) -> T + Sync,
+{
+    let jobs = jobs.min(items.len());
+    if jobs <= 1 {
+        return items.iter().map(f).collect();
+    }
+    let next = AtomicUsize::new(0);
+    let mut indexed: Vec<(usize, T)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = vec![];
+                    loop {
+                        let idx = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(idx) else {
+                            return done;
+                        };
+                        done.push((idx, f(item)));
+                    }
+                })
+            })
+            .collect();
+        let mut indexed = Vec::with_capacity(items.len());
+        for worker in workers {
+            match worker.join() {
+                Ok(done) => indexed.extend(done),
+                Err(payload) => panic::resume_unwind(payload),
+            }
+        }
+        indexed
+    });
+    indexed.sort_unstable_by_key(|(idx, _)| *idx);
+    indexed.into_iter().map(|(_, res)| res).collect()
+}
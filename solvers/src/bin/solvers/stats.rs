@@ -0,0 +1,133 @@
+use std::fmt;
+use std::time::Duration;
+
+use common::{ensure, Answer, Result};
+
+/// How many times a solver runs before and while being measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repetition {
+    pub warmup: usize,
+    pub runs: usize,
+}
+
+impl Default for Repetition {
+    /// A single measured run, like `AocSolver::timed_solve`.
+    fn default() -> Self {
+        Self { warmup: 0, runs: 1 }
+    }
+}
+
+impl Repetition {
+    /// Run `timed_solve` a few times without measuring it, then a few times to measure it.
+    ///
+    /// The answer of the last run is returned.
+    pub fn measure<F>(self, mut timed_solve: F) -> Result<(Answer, Timings)>
+    where
+        F: FnMut() -> Result<(Answer, Duration)>,
+    {
+        ensure!(self.runs != 0, "At least one measured run is needed");
+        for _ in 0..self.warmup {
+            timed_solve()?;
+        }
+        let mut samples = Vec::with_capacity(self.runs);
+        let (mut answer, t) = timed_solve()?;
+        samples.push(t);
+        for _ in 1..self.runs {
+            let (res, t) = timed_solve()?;
+            answer = res;
+            samples.push(t);
+        }
+        Ok((answer, Timings::new(samples)))
+    }
+}
+
+/// Durations of the measured runs of a solver, sorted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timings(Vec<Duration>);
+
+impl Timings {
+    /// Non-empty samples.
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        debug_assert!(!samples.is_empty(), "No sample");
+        samples.sort_unstable();
+        Self(samples)
+    }
+
+    pub fn runs(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn min(&self) -> Duration {
+        self.0[0]
+    }
+
+    pub fn median(&self) -> Duration {
+        let n = self.0.len();
+        if n % 2 == 0 {
+            (self.0[n / 2 - 1] + self.0[n / 2]) / 2
+        } else {
+            self.0[n / 2]
+        }
+    }
+
+    /// Time spent in the measured runs.
+    pub fn total(&self) -> Duration {
+        self.0.iter().sum()
+    }
+
+    #[allow(clippy::cast_possible_truncation)] // Not that many runs.
+    pub fn mean(&self) -> Duration {
+        self.0.iter().sum::<Duration>() / self.0.len() as u32
+    }
+
+    /// Nearest-rank percentile, `p` being in `0..=100`.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn percentile(&self, p: f64) -> Duration {
+        let rank = (p / 100.0 * self.0.len() as f64).ceil() as usize;
+        self.0[rank.clamp(1, self.0.len()) - 1]
+    }
+
+    pub fn p95(&self) -> Duration {
+        self.percentile(95.0)
+    }
+
+    /// Sample standard deviation, zero for a single run.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn stddev(&self) -> Duration {
+        let n = self.0.len();
+        if n < 2 {
+            return Duration::ZERO;
+        }
+        let mean = self.mean().as_secs_f64();
+        let variance = self
+            .0
+            .iter()
+            .map(|t| (t.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / (n - 1) as f64;
+        Duration::from_secs_f64(variance.sqrt())
+    }
+}
+
+impl fmt::Display for Timings {
+    /// The median, and other statistics if there are multiple runs.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.median())?;
+        if self.runs() > 1 {
+            write!(
+                f,
+                " (min {:?}, mean {:?}, p95 {:?}, stddev {:?}, {} runs)",
+                self.min(),
+                self.mean(),
+                self.p95(),
+                self.stddev(),
+                self.runs(),
+            )?;
+        }
+        Ok(())
+    }
+}
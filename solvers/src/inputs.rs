@@ -0,0 +1,110 @@
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use common::{Context, Day, Result, Year};
+
+/// Environment variable that can hold the directory of the puzzle inputs.
+pub const INPUTS_DIR_VAR: &str = "AOC_INPUTS";
+
+/// Examples are embedded in each solver module but big inputs are resolved at runtime
+/// from an inputs directory, so that the workspace compiles even without them.
+///
+/// The directory layout is:
+/// - `<dir>/YYYY/DD.txt` for my own inputs ;
+/// - `<dir>/other/<username>/YYYY/DD.txt` for other users' inputs ;
+/// - `<dir>/answers.txt` for the expected answers (see `common::answers`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputProvider {
+    dir: PathBuf,
+}
+
+impl Default for InputProvider {
+    /// The directory given by the `AOC_INPUTS` environment variable
+    /// or the "inputs" folder at the root of the workspace.
+    fn default() -> Self {
+        env::var_os(INPUTS_DIR_VAR).map_or_else(
+            || Self::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("../inputs")),
+            Self::new,
+        )
+    }
+}
+
+impl InputProvider {
+    /// Inputs from the given directory.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Where my input for the given puzzle should be.
+    #[must_use]
+    pub fn big_input_path(&self, year: Year, day: Day) -> PathBuf {
+        self.dir.join(Self::relative_path(year, day))
+    }
+
+    /// Where the input of another user for the given puzzle should be.
+    #[must_use]
+    pub fn other_input_path(&self, username: &str, year: Year, day: Day) -> PathBuf {
+        self.other_dir()
+            .join(username)
+            .join(Self::relative_path(year, day))
+    }
+
+    /// Where the inputs of other users are.
+    #[must_use]
+    pub fn other_dir(&self) -> PathBuf {
+        self.dir.join("other")
+    }
+
+    /// Where the expected answers are.
+    #[must_use]
+    pub fn answers_path(&self) -> PathBuf {
+        self.dir.join("answers.txt")
+    }
+
+    /// Names of the users whose inputs are available, sorted.
+    #[must_use]
+    pub fn other_usernames(&self) -> Vec<String> {
+        let mut names: Vec<_> = fs::read_dir(self.other_dir())
+            .map(|read_dir| {
+                read_dir
+                    .filter_map(|dir_entry| {
+                        let dir_entry = dir_entry.ok()?;
+                        dir_entry.file_type().ok()?.is_dir().then_some(())?;
+                        dir_entry.file_name().into_string().ok()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort_unstable();
+        names
+    }
+
+    /// My input for the given puzzle, or `None` when the file does not exist.
+    pub fn big_input(&self, year: Year, day: Day) -> Result<Option<String>> {
+        read_if_exists(&self.big_input_path(year, day))
+    }
+
+    /// The input of another user for the given puzzle, or `None` when the file does not exist.
+    pub fn other_input(&self, username: &str, year: Year, day: Day) -> Result<Option<String>> {
+        read_if_exists(&self.other_input_path(username, year, day))
+    }
+
+    fn relative_path(year: Year, day: Day) -> PathBuf {
+        PathBuf::from(i32::from(year).to_string()).join(format!("{:0>2}.txt", u8::from(day)))
+    }
+}
+
+fn read_if_exists(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
@@ -4,9 +4,12 @@ use crate::traits::AocSolver;
 
 #[macro_use]
 mod macros;
+pub mod inputs;
 mod traits;
 pub mod utils;
 
+pub use inputs::InputProvider;
+
 macro_rules! pub_mod {
     ($($year:literal => $($day:literal)*),* $(,)?) => {
         paste::paste! {
@@ -16,6 +19,9 @@ macro_rules! pub_mod {
                 }
             )*
 
+            /// The solver of the given puzzle and its examples.
+            ///
+            /// Big inputs are not embedded, see `InputProvider`.
             #[allow(clippy::zero_prefixed_literal)]
             pub fn aoc(year: Year, day: Day) -> Result<(Box<dyn AocSolver>, &'static [&'static str])> {
                 Ok(match (year, u8::from(day)) {
@@ -23,7 +29,7 @@ macro_rules! pub_mod {
                         $(
                             (Year::[<Year20 $year>], $day) => (
                                 Box::new(self::[<year20 $year>]::[<day $day>]::solver),
-                                &self::[<year20 $year>]::[<day $day>]::INPUTS,
+                                &self::[<year20 $year>]::[<day $day>]::EXAMPLES,
                             ),
                         )*
                     )*
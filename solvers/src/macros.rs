@@ -1,16 +1,7 @@
-macro_rules! include_input {
-    ($year:literal $day:literal) => {
-        include_str!(concat!(
-            "../../../inputs/20",
-            stringify!($year),
-            "/",
-            stringify!($day),
-            ".txt"
-        ))
-    };
-}
-
-/// Defines the public constant `INPUTS: [&str; _]` and write a test named `test_solver`.
+/// Defines the public constant `EXAMPLES: [&str; _]` and write a test named `test_solver`.
+///
+/// Examples are embedded while the big input is only read at runtime
+/// (see `InputProvider`), the test simply skips it when the file is missing.
 ///
 /// ## Usage
 /// ```text
@@ -19,13 +10,14 @@ macro_rules! include_input {
 ///     "2" => ("p1", "p2"), // both parts
 ///     "3" => ((), "p2"),   // Part2 only
 ///     "25" => "p1",        // Part1 only
-///     include_input!(15 01) => (12345, 67890),
+///     include_input!(15 01) => (12345, 67890), // mandatory and last
 /// }
 /// ```
+/// where `()` stands for no answer yet.
 /// or if I have attributes to give to the test function
 /// ```text
 /// test_solver! {
-///     #[cfg_attr(feature = "lp", ignore)] // too slow
+///     #[ignore] // too slow
 ///     {
 ///         // input => answers,
 ///     }
@@ -35,23 +27,34 @@ macro_rules! test_solver {
     (
         $(#[$attr:meta])*
         {
-            $(
-                $input:expr => $answers:expr
-            ),*
-            $(,)?
+            $($tokens:tt)*
         }
     ) => {
-        pub const INPUTS: [&str; count_exprs!($($input,)*)] = [$($input,)*];
+        test_solver!(@munch [$(#[$attr])*] [] $($tokens)*);
+    };
+    // The big input is the last one.
+    (
+        @munch [$(#[$attr:meta])*] [$($input:expr => $answers:expr,)*]
+        include_input!($year:literal $day:literal) => $big_answers:expr $(,)?
+    ) => {
+        pub const EXAMPLES: [&str; count_exprs!($($input,)*)] = [$($input,)*];
 
         #[test]
         $(#[$attr])*
         fn test_solver() -> ::common::Result<()> {
             use crate::traits::TestAnswers;
-            let inputs = INPUTS;
+            let year = concat!("20", stringify!($year)).parse()?;
+            let day = stringify!($day).parse()?;
+            let big_input = crate::inputs::InputProvider::default().big_input(year, day)?;
+            if big_input.is_none() {
+                eprintln!("No input for {year:?} {day:?}, only examples are tested.");
+            }
             #[allow(clippy::identity_op)]
-            let all_answers = [$($answers.test_answers()),*];
+            let all_answers = [$($answers.test_answers(),)* $big_answers.test_answers()];
+            let inputs = EXAMPLES.into_iter().map(Some).chain([big_input.as_deref()]);
             for (part_idx, part) in ::common::Part::ALL.into_iter().enumerate() {
-                for (test_idx, (input, answers)) in inputs.iter().zip(&all_answers).enumerate() {
+                for (test_idx, (input, answers)) in inputs.clone().zip(&all_answers).enumerate() {
+                    let Some(input) = input else { continue };
                     if let Some(answer) = answers[part_idx] {
                         assert_eq!(&solver(part, input)?, answer, "{:?} input #{}", part, test_idx);
                     }
@@ -60,14 +63,18 @@ macro_rules! test_solver {
             Ok(())
         }
     };
+    // Examples.
+    (
+        @munch [$(#[$attr:meta])*] [$($acc:tt)*]
+        $input:expr => $answers:expr, $($tokens:tt)*
+    ) => {
+        test_solver!(@munch [$(#[$attr])*] [$($acc)* $input => $answers,] $($tokens)*);
+    };
     (
-        $(
-            $input:expr => $answers:expr
-        ),*
-        $(,)?
+        $($tokens:tt)*
     ) => {
         test_solver! {{
-            $($input => $answers),*
+            $($tokens)*
         }}
     };
 }
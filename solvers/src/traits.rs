@@ -1,12 +1,14 @@
 use std::time::{Duration, Instant};
 
-use common::{Part, Result};
+use common::{Answer, Context, Part, Result};
 
-trait SolverAnswer: std::fmt::Display {}
+trait SolverAnswer: Sized {
+    fn into_answer(self) -> Result<Answer>;
+}
 
 pub trait AocSolver {
-    fn solve(&self, part: Part, input: &str) -> Result<String>;
-    fn timed_solve(&self, part: Part, input: &str) -> Result<(String, Duration)>;
+    fn solve(&self, part: Part, input: &str) -> Result<Answer>;
+    fn timed_solve(&self, part: Part, input: &str) -> Result<(Answer, Duration)>;
 }
 
 #[cfg(test)]
@@ -15,35 +17,42 @@ pub trait TestAnswers<T>: Sized {
     fn test_answers(&self) -> [Option<&T>; 2];
 }
 
-impl SolverAnswer for String {}
-impl SolverAnswer for &'static str {}
-impl SolverAnswer for u8 {}
-impl SolverAnswer for u16 {}
-impl SolverAnswer for u32 {}
-impl SolverAnswer for usize {}
-impl SolverAnswer for u64 {}
-impl SolverAnswer for u128 {}
-impl SolverAnswer for i8 {}
-impl SolverAnswer for i16 {}
-impl SolverAnswer for i32 {}
-impl SolverAnswer for isize {}
-impl SolverAnswer for i64 {}
-impl SolverAnswer for i128 {}
+macro_rules! impl_solver_answer {
+    ($($t:ty)*) => {
+        $(
+            impl SolverAnswer for $t {
+                #[inline]
+                fn into_answer(self) -> Result<Answer> {
+                    Ok(self.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_solver_answer!(Answer String &'static str u8 u16 u32 usize u64 i8 i16 i32 isize i64 i128);
+
+impl SolverAnswer for u128 {
+    fn into_answer(self) -> Result<Answer> {
+        let value = i128::try_from(self).context("The answer does not fit in an i128")?;
+        Ok(Answer::Int(value))
+    }
+}
 
 impl<T, F> AocSolver for F
 where
     T: SolverAnswer,
     F: Fn(Part, &str) -> Result<T>,
 {
-    fn solve(&self, part: Part, input: &str) -> Result<String> {
-        self(part, input).map(|t| t.to_string())
+    fn solve(&self, part: Part, input: &str) -> Result<Answer> {
+        self(part, input)?.into_answer()
     }
 
-    fn timed_solve(&self, part: Part, input: &str) -> Result<(String, Duration)> {
+    fn timed_solve(&self, part: Part, input: &str) -> Result<(Answer, Duration)> {
         let now = Instant::now();
         let t = self(part, input)?;
         let elapsed = now.elapsed();
-        Ok((t.to_string(), elapsed))
+        Ok((t.into_answer()?, elapsed))
     }
 }
 
@@ -61,6 +70,14 @@ impl<T: SolverAnswer> TestAnswers<T> for (T,) {
     }
 }
 
+/// No answer yet.
+#[cfg(test)]
+impl<T: SolverAnswer> TestAnswers<T> for () {
+    fn test_answers(&self) -> [Option<&T>; 2] {
+        [None, None]
+    }
+}
+
 #[cfg(test)]
 impl<T: SolverAnswer> TestAnswers<T> for ((), T) {
     fn test_answers(&self) -> [Option<&T>; 2] {
@@ -0,0 +1,73 @@
+/// Depth-first branch and bound: the maximum value among the states reachable from `start`.
+///
+/// - `value`: what a state is worth if we stop there.
+/// - `upper_bound`: what any state reachable from it could be worth at best.
+/// - `branches`: the next states.
+///
+/// The tighter the upper bound, the more branches are cut.
+/// Wrap values in `std::cmp::Reverse` to minimize instead.
+pub fn maximize<S, V, FV, FU, FB, I>(
+    start: S,
+    mut value: FV,
+    mut upper_bound: FU,
+    mut branches: FB,
+) -> V
+where
+    V: Copy + Ord,
+    FV: FnMut(&S) -> V,
+    FU: FnMut(&S) -> V,
+    FB: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    let mut best = value(&start);
+    let mut stack = vec![start];
+    while let Some(state) = stack.pop() {
+        best = best.max(value(&state));
+        if upper_bound(&state) <= best {
+            continue;
+        }
+        stack.extend(branches(&state));
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore]
+    fn test_knapsack() {
+        // (weight, value) sorted by decreasing value density, capacity 10.
+        let items = [(2_u32, 40_u32), (3, 50), (5, 60), (4, 40), (6, 30)];
+        // Index of the next item to consider, remaining capacity, total value.
+        let best = maximize(
+            (0, 10, 0),
+            |&(_, _, total)| total,
+            |&(idx, capacity, total)| {
+                // Fractional relaxation.
+                let mut capacity = capacity;
+                let mut bound = total;
+                for &(weight, value) in &items[idx..] {
+                    let taken = weight.min(capacity);
+                    bound += value * taken / weight + u32::from(value * taken % weight != 0);
+                    capacity -= taken;
+                }
+                bound
+            },
+            |&(idx, capacity, total)| {
+                items
+                    .get(idx)
+                    .into_iter()
+                    .flat_map(move |&(weight, value)| {
+                        let take = (weight <= capacity)
+                            .then(|| (idx + 1, capacity - weight, total + value));
+                        [Some((idx + 1, capacity, total)), take]
+                            .into_iter()
+                            .flatten()
+                    })
+            },
+        );
+        assert_eq!(best, 150);
+    }
+}
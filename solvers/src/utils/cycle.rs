@@ -0,0 +1,170 @@
+use std::hash::Hash;
+use std::ops::{Add, Mul, Sub};
+
+use common::hash::prelude::*;
+
+/// A sequence of states `x0, x1 = step(x0), ...` that eventually repeats:
+/// `x[i + period] == x[i]` for every `i >= start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Length of the prefix before the first period.
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The index before `start + period` of a state equal to `x[index]`.
+    #[must_use]
+    pub const fn reduce(self, index: usize) -> usize {
+        if index < self.start {
+            index
+        } else {
+            self.start + (index - self.start) % self.period
+        }
+    }
+
+    /// Extrapolate a quantity accumulated along the states (height, score...).
+    ///
+    /// `values[i]` is the quantity at `x[i]`, it needs `start + period + 1` values.
+    /// `None` if there are not enough values or if a conversion fails.
+    pub fn extrapolate<T>(self, values: &[T], target: usize) -> Option<T>
+    where
+        T: Copy + TryFrom<usize> + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        let end = self.start + self.period;
+        if values.len() <= end {
+            return None;
+        }
+        if target <= end {
+            return Some(values[target]);
+        }
+        let nb_periods = T::try_from((target - self.start) / self.period).ok()?;
+        let per_period = values[end] - values[self.start];
+        Some(values[self.reduce(target)] + nb_periods * per_period)
+    }
+}
+
+/// Find the cycle by hashing the key of every state.
+///
+/// The state is stepped in place and ends at `x[start + period]`.
+/// `key` is called once on every state, in order, which is a good place to record quantities.
+pub fn detect<S, K, F, G>(state: &mut S, mut step: F, mut key: G) -> Cycle
+where
+    K: Hash + Eq,
+    F: FnMut(&mut S),
+    G: FnMut(&S) -> K,
+{
+    let mut seen = HashMap::new();
+    for index in 0.. {
+        if let Some(start) = seen.insert(key(state), index) {
+            return Cycle {
+                start,
+                period: index - start,
+            };
+        }
+        step(state);
+    }
+    unreachable!("a sequence of states is either infinite or cyclic")
+}
+
+/// Find the cycle with Brent's algorithm, comparing states without storing them.
+pub fn brent<S, F>(x0: S, mut step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = step(&x0);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+    let mut tortoise = x0.clone();
+    let mut hare = x0;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, period }
+}
+
+/// Find the cycle with Floyd's "tortoise and hare" algorithm, comparing states without storing them.
+pub fn floyd<S, F>(x0: S, mut step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    let mut tortoise = step(&x0);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    let mut tortoise = x0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    let mut period = 1;
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+    Cycle { start, period }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    #[ignore]
+    fn test_cycle() {
+        let f = |x: &u32| (x * x + 1) % 255;
+        let mut x = 3;
+        let mut xs = vec![];
+        let cycle = detect(
+            &mut x,
+            |x| *x = f(x),
+            |x| {
+                xs.push(*x);
+                *x
+            },
+        );
+        assert_eq!(xs[cycle.start], x);
+        assert!(xs[..xs.len() - 1].iter().all_unique());
+        assert_eq!(brent(3, f), cycle);
+        assert_eq!(floyd(3, f), cycle);
+        for index in 0..1000 {
+            let expected = (0..index).fold(3, |x, _| f(&x));
+            assert_eq!(xs[cycle.reduce(index)], expected);
+        }
+        let cycle = Cycle {
+            start: 2,
+            period: 3,
+        };
+        let heights = [0, 1, 5, 6, 8, 10];
+        assert_eq!(cycle.extrapolate(&heights, 4), Some(8));
+        assert_eq!(cycle.extrapolate(&heights, 8), Some(15));
+        assert_eq!(cycle.extrapolate(&heights, 9), Some(16));
+        assert_eq!(cycle.extrapolate(&heights[..5], 9), None);
+    }
+}
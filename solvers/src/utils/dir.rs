@@ -0,0 +1,233 @@
+use crate::utils::Point2;
+
+/// The four cardinal directions, north being up (towards lower rows).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir4 {
+    N,
+    E,
+    S,
+    W,
+}
+
+/// The four cardinal directions and the four diagonal ones, north being up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir4 {
+    /// Clockwise, from north.
+    pub const ALL: [Self; 4] = [Self::N, Self::E, Self::S, Self::W];
+
+    /// A quarter turn clockwise.
+    #[must_use]
+    #[inline]
+    pub const fn turn_right(self) -> Self {
+        match self {
+            Self::N => Self::E,
+            Self::E => Self::S,
+            Self::S => Self::W,
+            Self::W => Self::N,
+        }
+    }
+
+    /// A quarter turn counterclockwise.
+    #[must_use]
+    #[inline]
+    pub const fn turn_left(self) -> Self {
+        match self {
+            Self::N => Self::W,
+            Self::E => Self::N,
+            Self::S => Self::E,
+            Self::W => Self::S,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn opposite(self) -> Self {
+        match self {
+            Self::N => Self::S,
+            Self::E => Self::W,
+            Self::S => Self::N,
+            Self::W => Self::E,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn is_vertical(self) -> bool {
+        matches!(self, Self::N | Self::S)
+    }
+
+    /// The move of one step in this direction.
+    #[must_use]
+    #[inline]
+    pub const fn offset(self) -> Point2<isize> {
+        match self {
+            Self::N => Point2::new(0, -1),
+            Self::E => Point2::new(1, 0),
+            Self::S => Point2::new(0, 1),
+            Self::W => Point2::new(-1, 0),
+        }
+    }
+
+    /// One step in this direction, if it stays inside a `width` x `height` grid.
+    #[must_use]
+    #[inline]
+    pub const fn step(
+        self,
+        p: Point2<usize>,
+        width: usize,
+        height: usize,
+    ) -> Option<Point2<usize>> {
+        self.step_by(1, p, width, height)
+    }
+
+    /// Some steps in this direction, if it stays inside a `width` x `height` grid.
+    #[must_use]
+    pub const fn step_by(
+        self,
+        amount: usize,
+        Point2 { x, y }: Point2<usize>,
+        width: usize,
+        height: usize,
+    ) -> Option<Point2<usize>> {
+        let (x, y) = match self {
+            Self::N => match y.checked_sub(amount) {
+                Some(y) => (x, y),
+                None => return None,
+            },
+            Self::E => (x.saturating_add(amount), y),
+            Self::S => (x, y.saturating_add(amount)),
+            Self::W => match x.checked_sub(amount) {
+                Some(x) => (x, y),
+                None => return None,
+            },
+        };
+        if x < width && y < height {
+            Some(Point2::new(x, y))
+        } else {
+            None
+        }
+    }
+}
+
+impl Dir8 {
+    /// Clockwise, from north.
+    pub const ALL: [Self; 8] = [
+        Self::N,
+        Self::NE,
+        Self::E,
+        Self::SE,
+        Self::S,
+        Self::SW,
+        Self::W,
+        Self::NW,
+    ];
+
+    /// An eighth of a turn clockwise.
+    #[must_use]
+    #[inline]
+    pub const fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// An eighth of a turn counterclockwise.
+    #[must_use]
+    #[inline]
+    pub const fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn is_diagonal(self) -> bool {
+        matches!(self, Self::NE | Self::SE | Self::SW | Self::NW)
+    }
+
+    /// The move of one step in this direction.
+    #[must_use]
+    #[inline]
+    pub const fn offset(self) -> Point2<isize> {
+        match self {
+            Self::N => Point2::new(0, -1),
+            Self::NE => Point2::new(1, -1),
+            Self::E => Point2::new(1, 0),
+            Self::SE => Point2::new(1, 1),
+            Self::S => Point2::new(0, 1),
+            Self::SW => Point2::new(-1, 1),
+            Self::W => Point2::new(-1, 0),
+            Self::NW => Point2::new(-1, -1),
+        }
+    }
+
+    /// One step in this direction, if it stays inside a `width` x `height` grid.
+    #[must_use]
+    #[inline]
+    pub const fn step(
+        self,
+        p: Point2<usize>,
+        width: usize,
+        height: usize,
+    ) -> Option<Point2<usize>> {
+        match p.checked_add_signed(self.offset()) {
+            Some(p) if p.in_bounds(width, height) => Some(p),
+            _ => None,
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    #[inline]
+    fn from(dir: Dir4) -> Self {
+        match dir {
+            Dir4::N => Self::N,
+            Dir4::E => Self::E,
+            Dir4::S => Self::S,
+            Dir4::W => Self::W,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore]
+    fn test_directions() {
+        for dir in Dir4::ALL {
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.opposite());
+            assert_eq!(-dir.offset(), dir.opposite().offset());
+            assert_eq!(Dir8::from(dir).offset(), dir.offset());
+        }
+        for dir in Dir8::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(-dir.offset(), dir.opposite().offset());
+            assert_eq!(dir.offset().chebyshev(Point2::default()), 1);
+        }
+        let p = Point2::new(0, 2);
+        assert_eq!(Dir4::W.step(p, 3, 3), None);
+        assert_eq!(Dir4::S.step(p, 3, 3), None);
+        assert_eq!(Dir4::N.step_by(2, p, 3, 3), Some(Point2::new(0, 0)));
+        assert_eq!(Dir4::E.step_by(usize::MAX, p, 3, 3), None);
+        assert_eq!(Dir8::NE.step(p, 3, 3), Some(Point2::new(1, 1)));
+        assert_eq!(Point2::new(1, -3).manhattan(Point2::new(-2, 1)), 7);
+        assert_eq!(Point2::new(1_usize, 5).manhattan(Point2::new(4, 2)), 6);
+    }
+}
@@ -1,4 +1,8 @@
-use common::Result;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::utils::Point2;
+use common::{ensure, Result};
 
 pub fn parse<'a, It, T, F>(s: It, mut parser: F) -> Result<Vec<Vec<T>>>
 where
@@ -75,3 +79,300 @@ pub fn neighbors(
     }
     res
 }
+
+/// Rectangular grid of cells, stored row by row and indexed by `(row, column)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    nrows: usize,
+    ncols: usize,
+}
+
+impl<T> Grid<T> {
+    /// Non-empty rows of the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let nrows = rows.len();
+        let ncols = rows.first().map_or(0, Vec::len);
+        ensure!(ncols != 0, "Empty grid");
+        ensure!(
+            rows.iter().all(|row| row.len() == ncols),
+            "The grid is not rectangular"
+        );
+        let data = rows.into_iter().flatten().collect();
+        Ok(Self { data, nrows, ncols })
+    }
+
+    /// Each cell is given by its location. It panics if the grid would be empty.
+    pub fn from_fn<F>(nrows: usize, ncols: usize, mut f: F) -> Self
+    where
+        F: FnMut((usize, usize)) -> T,
+    {
+        assert!(nrows != 0 && ncols != 0, "Empty grid");
+        let data = (0..nrows)
+            .flat_map(|r| (0..ncols).map(move |c| (r, c)))
+            .map(&mut f)
+            .collect();
+        Self { data, nrows, ncols }
+    }
+
+    /// Parse each line as a row, each char as a cell.
+    pub fn parse<F>(s: &str, parser: F) -> Result<Self>
+    where
+        F: FnMut(char) -> Result<T>,
+    {
+        Self::from_rows(parse(s.lines(), parser)?)
+    }
+
+    /// Parse each line as a row, each char as a cell, knowing its location.
+    pub fn parse_with_loc<F>(s: &str, parser: F) -> Result<Self>
+    where
+        F: FnMut((usize, usize), char) -> Result<T>,
+    {
+        Self::from_rows(parse_with_loc(s.lines(), parser)?)
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    /// `(nrows, ncols)`
+    #[must_use]
+    #[inline]
+    pub const fn shape(&self) -> (usize, usize) {
+        (self.nrows, self.ncols)
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn in_bounds(&self, (r, c): (usize, usize)) -> bool {
+        r < self.nrows && c < self.ncols
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn get(&self, loc: (usize, usize)) -> Option<&T> {
+        self.in_bounds(loc).then(|| &self[loc])
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn get_mut(&mut self, loc: (usize, usize)) -> Option<&mut T> {
+        if self.in_bounds(loc) {
+            Some(&mut self[loc])
+        } else {
+            None
+        }
+    }
+
+    /// Cells row by row.
+    #[inline]
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    /// Cells row by row.
+    #[inline]
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.data.iter_mut()
+    }
+
+    /// Cells row by row, with their locations.
+    pub fn enumerate(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let ncols = self.ncols;
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(idx, cell)| ((idx / ncols, idx % ncols), cell))
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn row(&self, r: usize) -> &[T] {
+        &self.data[r * self.ncols..(r + 1) * self.ncols]
+    }
+
+    pub fn rows(&self) -> std::slice::ChunksExact<'_, T> {
+        self.data.chunks_exact(self.ncols)
+    }
+
+    pub fn column(&self, c: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(c < self.ncols, "Column out of bounds");
+        self.data.iter().skip(c).step_by(self.ncols)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.ncols).map(|c| self.column(c))
+    }
+
+    /// The location of the first cell (row by row) satisfying the predicate.
+    pub fn find<P>(&self, predicate: P) -> Option<(usize, usize)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let idx = self.data.iter().position(predicate)?;
+        Some((idx / self.ncols, idx % self.ncols))
+    }
+
+    /// Neighbors of `loc` in the grid.
+    #[must_use]
+    #[inline]
+    pub fn neighbors(&self, loc: (usize, usize), diagonally: bool) -> Vec<(usize, usize)> {
+        neighbors(loc, self.nrows, self.ncols, diagonally)
+    }
+
+    #[must_use]
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            data: self.data.iter().map(f).collect(),
+            nrows: self.nrows,
+            ncols: self.ncols,
+        }
+    }
+
+    /// Display the grid with one char per cell.
+    pub fn display<'a, F>(&'a self, to_char: F) -> impl fmt::Display + 'a
+    where
+        F: Fn(&T) -> char + 'a,
+    {
+        GridDisplay {
+            grid: self,
+            to_char,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// It panics if the grid would be empty.
+    #[must_use]
+    pub fn filled(nrows: usize, ncols: usize, value: T) -> Self {
+        assert!(nrows != 0 && ncols != 0, "Empty grid");
+        Self {
+            data: vec![value; nrows * ncols],
+            nrows,
+            ncols,
+        }
+    }
+
+    /// Rows become columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.ncols, self.nrows, |(r, c)| self[(c, r)].clone())
+    }
+
+    /// Rotate a quarter turn clockwise.
+    #[must_use]
+    pub fn rotate_right(&self) -> Self {
+        let nrows = self.nrows;
+        Self::from_fn(self.ncols, nrows, |(r, c)| self[(nrows - 1 - c, r)].clone())
+    }
+
+    /// Rotate a quarter turn counterclockwise.
+    #[must_use]
+    pub fn rotate_left(&self) -> Self {
+        let ncols = self.ncols;
+        Self::from_fn(ncols, self.nrows, |(r, c)| self[(c, ncols - 1 - r)].clone())
+    }
+
+    /// Mirror left to right.
+    #[must_use]
+    pub fn flip_horizontal(&self) -> Self {
+        let ncols = self.ncols;
+        Self::from_fn(self.nrows, ncols, |(r, c)| self[(r, ncols - 1 - c)].clone())
+    }
+
+    /// Mirror top to bottom.
+    #[must_use]
+    pub fn flip_vertical(&self) -> Self {
+        let nrows = self.nrows;
+        Self::from_fn(nrows, self.ncols, |(r, c)| self[(nrows - 1 - r, c)].clone())
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, (r, c): (usize, usize)) -> &T {
+        assert!(c < self.ncols, "Column out of bounds");
+        &self.data[r * self.ncols + c]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut T {
+        assert!(c < self.ncols, "Column out of bounds");
+        &mut self.data[r * self.ncols + c]
+    }
+}
+
+impl<T> Index<Point2<usize>> for Grid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, p: Point2<usize>) -> &T {
+        &self[p.loc()]
+    }
+}
+
+impl<T> IndexMut<Point2<usize>> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, p: Point2<usize>) -> &mut T {
+        &mut self[p.loc()]
+    }
+}
+
+struct GridDisplay<'a, T, F> {
+    grid: &'a Grid<T>,
+    to_char: F,
+}
+
+impl<T, F: Fn(&T) -> char> fmt::Display for GridDisplay<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.grid.rows().try_for_each(|row| {
+            row.iter()
+                .try_for_each(|cell| write!(f, "{}", (self.to_char)(cell)))?;
+            writeln!(f)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore]
+    fn test_transformations() -> Result<()> {
+        let grid = Grid::parse("abc\ndef\n", Ok)?;
+        let text = |g: &Grid<char>| g.display(|ch| *ch).to_string();
+        assert_eq!(grid.shape(), (2, 3));
+        assert_eq!(grid[(1, 0)], 'd');
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.find(|ch| *ch == 'e'), Some((1, 1)));
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(text(&grid.transpose()), "ad\nbe\ncf\n");
+        assert_eq!(text(&grid.rotate_right()), "da\neb\nfc\n");
+        assert_eq!(text(&grid.rotate_left()), "cf\nbe\nad\n");
+        assert_eq!(text(&grid.flip_horizontal()), "cba\nfed\n");
+        assert_eq!(text(&grid.flip_vertical()), "def\nabc\n");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+        assert!(Grid::parse("ab\nc\n", Ok).is_err());
+        assert!(Grid::parse("", Ok).is_err());
+        assert!(std::panic::catch_unwind(|| grid[(0, 3)]).is_err());
+        assert!(std::panic::catch_unwind(|| Grid::filled(2, 0, 'a')).is_err());
+        assert!(std::panic::catch_unwind(|| Grid::from_fn(0, 2, |_| 'a')).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,324 @@
+use std::iter::once;
+
+use num_integer::Integer;
+
+/// Half-open interval `start..end` of integers, empty when `end <= start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+/// Sorted non-empty intervals, neither overlapping nor touching each other.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T>(Vec<Interval<T>>);
+
+/// N-dimensional box, product of intervals: `[x, y, z]` for a usual cuboid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid<T, const N: usize>(pub [Interval<T>; N]);
+
+impl<T: Integer + Copy> Interval<T> {
+    #[inline]
+    pub const fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    /// The interval `first..=last`.
+    #[inline]
+    pub fn inclusive(first: T, last: T) -> Self {
+        Self::new(first, last + T::one())
+    }
+
+    #[inline]
+    pub fn is_empty(self) -> bool {
+        self.end <= self.start
+    }
+
+    #[inline]
+    pub fn non_empty(self) -> Option<Self> {
+        (!self.is_empty()).then_some(self)
+    }
+
+    /// Number of integers in it.
+    #[inline]
+    pub fn len(self) -> T {
+        if self.is_empty() {
+            T::zero()
+        } else {
+            self.end - self.start
+        }
+    }
+
+    #[inline]
+    pub fn contains(self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// The common part, if any.
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        Self::new(self.start.max(other.start), self.end.min(other.end)).non_empty()
+    }
+
+    /// The parts below `value` and from `value` on, if not empty.
+    pub fn split_at(self, value: T) -> [Option<Self>; 2] {
+        [
+            Self::new(self.start, self.end.min(value)).non_empty(),
+            Self::new(self.start.max(value), self.end).non_empty(),
+        ]
+    }
+
+    /// The parts below and above `other`, if not empty.
+    pub fn difference(self, other: Self) -> [Option<Self>; 2] {
+        if other.is_empty() {
+            return [self.non_empty(), None];
+        }
+        [
+            Self::new(self.start, self.end.min(other.start)).non_empty(),
+            Self::new(self.start.max(other.end), self.end).non_empty(),
+        ]
+    }
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<T: Integer + Copy> IntervalSet<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The sorted intervals.
+    #[must_use]
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.0.iter().copied()
+    }
+
+    /// Number of integers in it.
+    pub fn total_len(&self) -> T {
+        self.0.iter().fold(T::zero(), |total, iv| total + iv.len())
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self.0.partition_point(|iv| iv.end <= value);
+        self.0.get(idx).is_some_and(|iv| iv.contains(value))
+    }
+
+    /// Add an interval, merged with the ones it overlaps or touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        let lo = self.0.partition_point(|iv| iv.end < interval.start);
+        let hi = self.0.partition_point(|iv| iv.start <= interval.end);
+        let merged = if lo < hi {
+            Interval::new(
+                interval.start.min(self.0[lo].start),
+                interval.end.max(self.0[hi - 1].end),
+            )
+        } else {
+            interval
+        };
+        self.0.splice(lo..hi, once(merged));
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.extend(other.iter());
+        result
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut i, mut j) = (0, 0);
+        let mut result = vec![];
+        while let (Some(a), Some(b)) = (self.0.get(i), other.0.get(j)) {
+            result.extend(a.intersection(*b));
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self(result)
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        match (self.0.first(), self.0.last()) {
+            (Some(first), Some(last)) => {
+                self.intersection(&other.complement(Interval::new(first.start, last.end)))
+            }
+            _ => Self::new(),
+        }
+    }
+
+    /// What is within `bounds` but not in this set.
+    #[must_use]
+    pub fn complement(&self, bounds: Interval<T>) -> Self {
+        Self(self.gaps(bounds).collect())
+    }
+
+    /// Lazy version of `complement`.
+    pub fn gaps(&self, bounds: Interval<T>) -> impl Iterator<Item = Interval<T>> + '_ {
+        let mut cursor = bounds.start;
+        self.0
+            .iter()
+            .map(Some)
+            .chain(once(None))
+            .filter_map(move |iv| {
+                let end = iv.map_or(bounds.end, |iv| iv.start.min(bounds.end));
+                let gap = Interval::new(cursor, end).non_empty();
+                if let Some(iv) = iv {
+                    cursor = cursor.max(iv.end);
+                }
+                gap
+            })
+    }
+}
+
+impl<T: Integer + Copy> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Self(interval.non_empty().into_iter().collect())
+    }
+}
+
+impl<T: Integer + Copy> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        self.0.extend(iter.into_iter().filter(|iv| !iv.is_empty()));
+        self.0.sort_unstable_by_key(|iv| iv.start);
+        self.0.dedup_by(|next, prev| {
+            let merge = next.start <= prev.end;
+            if merge {
+                prev.end = prev.end.max(next.end);
+            }
+            merge
+        });
+    }
+}
+
+impl<T: Integer + Copy> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Integer + Copy, const N: usize> Cuboid<T, N> {
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().any(|iv| iv.is_empty())
+    }
+
+    /// Number of integer points in it.
+    pub fn volume(&self) -> T {
+        self.0.iter().fold(T::one(), |volume, iv| volume * iv.len())
+    }
+
+    pub fn contains(&self, point: [T; N]) -> bool {
+        self.0.iter().zip(point).all(|(iv, x)| iv.contains(x))
+    }
+
+    /// The common part, if any.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut result = *self;
+        for (a, b) in result.0.iter_mut().zip(other.0) {
+            *a = a.intersection(b)?;
+        }
+        Some(result)
+    }
+
+    /// At most `2 * N` disjoint boxes covering what is not in `other`.
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        let Some(common) = self.intersection(other) else {
+            return if self.is_empty() { vec![] } else { vec![*self] };
+        };
+        let mut rest = *self;
+        let mut pieces = vec![];
+        for dim in 0..N {
+            for part in rest.0[dim].difference(common.0[dim]).into_iter().flatten() {
+                let mut piece = rest;
+                piece.0[dim] = part;
+                pieces.push(piece);
+            }
+            rest.0[dim] = common.0[dim];
+        }
+        pieces
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore]
+    fn test_intervals() {
+        let iv = Interval::inclusive(2, 5);
+        assert_eq!(iv.len(), 4);
+        assert_eq!(
+            iv.split_at(4),
+            [Some(Interval::new(2, 4)), Some(Interval::new(4, 6))]
+        );
+        assert_eq!(iv.split_at(9), [Some(iv), None]);
+        assert_eq!(
+            iv.difference(Interval::new(3, 4)),
+            [Some(Interval::new(2, 3)), Some(Interval::new(4, 6))]
+        );
+        assert_eq!(iv.intersection(Interval::new(6, 9)), None);
+
+        let a: IntervalSet<i32> = [
+            Interval::new(0, 3),
+            Interval::new(8, 10),
+            Interval::new(3, 5),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(a.intervals(), [Interval::new(0, 5), Interval::new(8, 10)]);
+        assert_eq!(a.total_len(), 7);
+        assert!(a.contains(9) && !a.contains(5) && !a.contains(10));
+        let b = IntervalSet::from(Interval::new(4, 9));
+        assert_eq!(a.union(&b).intervals(), [Interval::new(0, 10)]);
+        assert_eq!(
+            a.intersection(&b).intervals(),
+            [Interval::new(4, 5), Interval::new(8, 9)]
+        );
+        assert_eq!(
+            a.difference(&b).intervals(),
+            [Interval::new(0, 4), Interval::new(9, 10)]
+        );
+        assert_eq!(
+            a.complement(Interval::new(-2, 9)).intervals(),
+            [Interval::new(-2, 0), Interval::new(5, 8)]
+        );
+
+        let c = Cuboid([Interval::new(0, 4); 3]);
+        let d = Cuboid([
+            Interval::new(1, 2),
+            Interval::new(-5, 5),
+            Interval::new(3, 9),
+        ]);
+        let pieces = c.difference(&d);
+        assert_eq!(pieces.len(), 3);
+        assert_eq!(pieces.iter().map(Cuboid::volume).sum::<i32>(), 64 - 4);
+        assert!(pieces.iter().all(|piece| piece.intersection(&d).is_none()));
+        assert!(c.difference(&c).is_empty());
+    }
+}
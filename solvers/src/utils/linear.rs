@@ -0,0 +1,105 @@
+use std::iter::once;
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+
+use common::{ensure, format_err, Result};
+
+/// Exact fraction of big integers, it can not overflow.
+pub type Rational = BigRational;
+
+fn is_zero(q: &Rational) -> bool {
+    q.numer() == &BigInt::default()
+}
+
+/// Solve `a * x == b` exactly with Gauss-Jordan elimination.
+///
+/// There can be more equations than unknowns but the solution must be unique.
+pub fn solve<T>(a: &[Vec<T>], b: &[T]) -> Result<Vec<Rational>>
+where
+    T: Clone + Into<BigInt>,
+{
+    ensure!(a.len() == b.len(), "As many right-hand sides as equations");
+    let n = a.first().map_or(0, Vec::len);
+    ensure!(
+        a.iter().all(|row| row.len() == n),
+        "The matrix is not rectangular"
+    );
+    let mut rows: Vec<Vec<Rational>> = a
+        .iter()
+        .zip(b)
+        .map(|(row, rhs)| {
+            row.iter()
+                .chain(once(rhs))
+                .map(|x| Rational::from_integer(x.clone().into()))
+                .collect()
+        })
+        .collect();
+    for col in 0..n {
+        let pivot = (col..rows.len())
+            .find(|&r| !is_zero(&rows[r][col]))
+            .ok_or_else(|| format_err!("The system does not have a unique solution"))?;
+        rows.swap(col, pivot);
+        let pivot = rows[col][col].clone();
+        for x in &mut rows[col][col..] {
+            *x /= &pivot;
+        }
+        let pivot_row = rows[col].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            if r == col || is_zero(&row[col]) {
+                continue;
+            }
+            let factor = row[col].clone();
+            for (x, p) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *x -= &factor * p;
+            }
+        }
+    }
+    ensure!(
+        rows[n..].iter().all(|row| is_zero(&row[n])),
+        "The system is inconsistent"
+    );
+    Ok(rows
+        .into_iter()
+        .take(n)
+        .map(|mut row| row.swap_remove(n))
+        .collect())
+}
+
+/// Solve `a * x == b` exactly when the unique solution is made of integers.
+pub fn solve_integral<T>(a: &[Vec<T>], b: &[T]) -> Result<Vec<i128>>
+where
+    T: Clone + Into<BigInt>,
+{
+    solve(a, b)?
+        .into_iter()
+        .map(|q| {
+            ensure!(q.is_integer(), "Not an integer: {}", q);
+            Ok(i128::try_from(q.to_integer())?)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore]
+    fn test_solve() -> Result<()> {
+        let a = vec![vec![2, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]];
+        assert_eq!(solve_integral(&a, &[8, -11, -3])?, [2, 3, -1]);
+        let x = solve(&[vec![3, 0], vec![0, 4], vec![3, 4]], &[1, 1, 2])?;
+        assert_eq!(
+            x,
+            [
+                Rational::new(1.into(), 3.into()),
+                Rational::new(1.into(), 4.into())
+            ]
+        );
+        assert!(solve(&[vec![3, 0], vec![0, 4], vec![3, 4]], &[1, 1, 3]).is_err());
+        assert!(solve(&[vec![1, 2], vec![2, 4]], &[1, 2]).is_err());
+        assert!(solve_integral(&[vec![2]], &[1]).is_err());
+        Ok(())
+    }
+}
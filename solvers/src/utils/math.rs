@@ -0,0 +1,215 @@
+use common::hash::prelude::*;
+use common::{ensure, format_err, Result};
+
+// Any primitive integer type fitting in `i128` can be used. Computations are done with `i128`
+// without overflowing, errors are for invalid arguments or results not fitting in that type.
+
+/// Back to a smaller type.
+fn narrow<T: TryFrom<i128>>(n: i128) -> Result<T> {
+    T::try_from(n).map_err(|_| format_err!("{} does not fit in the result type", n))
+}
+
+fn positive(m: impl Into<i128>) -> Result<i128> {
+    let m = m.into();
+    ensure!(m > 0, "The modulus must be positive, not {}", m);
+    Ok(m)
+}
+
+/// `(a + b) % m` for `a` and `b` in `0..m`.
+const fn add_mod(a: i128, b: i128, m: i128) -> i128 {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// `(a * b) % m` for `a` and `b` in `0..m`.
+fn mul_mod(mut a: i128, mut b: i128, m: i128) -> i128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+    let mut res = 0;
+    while b != 0 {
+        if b & 1 == 1 {
+            res = add_mod(res, a, m);
+        }
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+    res
+}
+
+/// `(gcd, x, y)` such that `a * x + b * y == gcd`.
+fn ext_gcd_i128(a: i128, b: i128) -> Result<(i128, i128, i128)> {
+    ensure!(
+        a != i128::MIN && b != i128::MIN,
+        "i128::MIN is not supported"
+    );
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    Ok(if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    })
+}
+
+fn mod_inv_i128(a: i128, m: i128) -> Result<i128> {
+    let (gcd, x, _) = ext_gcd_i128(a.rem_euclid(m), m)?;
+    ensure!(gcd == 1, "{} has no inverse modulo {}", a, m);
+    Ok(x.rem_euclid(m))
+}
+
+fn mod_pow_i128(base: i128, mut exp: i128, m: i128) -> i128 {
+    let mut base = base.rem_euclid(m);
+    let mut res = 1 % m;
+    while exp != 0 {
+        if exp & 1 == 1 {
+            res = mul_mod(res, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    res
+}
+
+/// Extended Euclidean algorithm: `(gcd, x, y)` such that `a * x + b * y == gcd >= 0`.
+pub fn ext_gcd<T>(a: T, b: T) -> Result<(T, T, T)>
+where
+    T: Into<i128> + TryFrom<i128>,
+{
+    let (gcd, x, y) = ext_gcd_i128(a.into(), b.into())?;
+    Ok((narrow(gcd)?, narrow(x)?, narrow(y)?))
+}
+
+/// `base.pow(exp) % modulus`, in `0..modulus`.
+pub fn mod_pow<T>(base: T, exp: T, modulus: T) -> Result<T>
+where
+    T: Into<i128> + TryFrom<i128>,
+{
+    let m = positive(modulus)?;
+    let exp = exp.into();
+    ensure!(exp >= 0, "Negative exponent: {}", exp);
+    narrow(mod_pow_i128(base.into(), exp, m))
+}
+
+/// The inverse of `a` modulo `modulus`, in `0..modulus`.
+pub fn mod_inv<T>(a: T, modulus: T) -> Result<T>
+where
+    T: Into<i128> + TryFrom<i128>,
+{
+    let m = positive(modulus)?;
+    narrow(mod_inv_i128(a.into(), m)?)
+}
+
+/// Chinese remainder theorem: solve `x == residue (mod modulus)` for all given pairs.
+///
+/// Moduli do not need to be coprime. The solution is `(x, lcm of moduli)` with `x` in `0..lcm`.
+pub fn crt<T, I>(congruences: I) -> Result<(T, T)>
+where
+    T: Into<i128> + TryFrom<i128>,
+    I: IntoIterator<Item = (T, T)>,
+{
+    let (mut x, mut lcm) = (0_i128, 1);
+    for (residue, m) in congruences {
+        let m = positive(m)?;
+        let residue = residue.into().rem_euclid(m);
+        let (gcd, _, _) = ext_gcd_i128(lcm, m)?;
+        let diff = residue - x.rem_euclid(m);
+        ensure!(
+            diff % gcd == 0,
+            "No solution: {} mod {} and {} mod {}",
+            x,
+            lcm,
+            residue,
+            m,
+        );
+        // x + lcm * k == residue (mod m)  <=>  (lcm / gcd) * k == diff / gcd (mod m / gcd)
+        let m_gcd = m / gcd;
+        let k = mul_mod(
+            (diff / gcd).rem_euclid(m_gcd),
+            mod_inv_i128(lcm / gcd, m_gcd)?,
+            m_gcd,
+        );
+        let new_lcm = (lcm / gcd)
+            .checked_mul(m)
+            .ok_or_else(|| format_err!("The lcm of the moduli overflows i128"))?;
+        // `lcm * k < new_lcm` so it does not overflow.
+        x = add_mod(x, lcm * k, new_lcm);
+        lcm = new_lcm;
+    }
+    Ok((narrow(x)?, narrow(lcm)?))
+}
+
+/// Baby-step giant-step: the smallest `x >= 0` such that `base.pow(x) == target (mod modulus)`.
+///
+/// `base` must be invertible modulo `modulus`.
+/// It takes about `sqrt(modulus)` steps and as much memory.
+pub fn discrete_log<T>(base: T, target: T, modulus: T) -> Result<Option<T>>
+where
+    T: Into<i128> + TryFrom<i128>,
+{
+    let m = positive(modulus)?;
+    let base = base.into().rem_euclid(m);
+    let target = target.into().rem_euclid(m);
+    let inv = mod_inv_i128(base, m)?;
+    // ceil(sqrt(m)), float imprecision is corrected after.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    let mut nb_steps = (m as f64).sqrt() as i128;
+    while nb_steps.checked_mul(nb_steps).is_some_and(|sq| sq < m) {
+        nb_steps += 1;
+    }
+    let capacity = usize::try_from(nb_steps)?;
+    let mut baby_steps = HashMap::with_capacity(capacity);
+    let mut value = 1 % m;
+    for j in 0..nb_steps {
+        baby_steps.entry(value).or_insert(j);
+        value = mul_mod(value, base, m);
+    }
+    // target * inv^(i * nb_steps) == base^j  <=>  base^(i * nb_steps + j) == target
+    let giant_step = mod_pow_i128(inv, nb_steps, m);
+    let mut value = target;
+    for i in 0..nb_steps {
+        if let Some(j) = baby_steps.get(&value) {
+            return narrow(i * nb_steps + j).map(Some);
+        }
+        value = mul_mod(value, giant_step, m);
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore]
+    fn test_math() -> Result<()> {
+        assert_eq!(ext_gcd(240, 46)?, (2, -9, 47));
+        assert_eq!(mod_inv(3, 11)?, 4);
+        assert!(mod_inv(4, 12).is_err());
+        assert_eq!(mod_pow(4_u64, 13, 497)?, 445);
+        assert_eq!(mod_pow(2_i128, 200, i128::MAX)?, 1 << 73);
+        assert!(mod_pow(2, 3, 0).is_err());
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)])?, (23, 105));
+        assert_eq!(crt([(2, 4), (4, 6)])?, (10, 12));
+        assert!(crt([(1, 4), (2, 6)]).is_err());
+        assert!(crt([(0, i64::MAX), (0, i64::MAX - 1)]).is_err());
+        assert_eq!(
+            crt([(0, i64::MAX), (0, i64::MAX - 1)].map(|(r, m)| (i128::from(r), i128::from(m))))?.1,
+            i128::from(i64::MAX) * i128::from(i64::MAX - 1)
+        );
+        assert_eq!(discrete_log(7_u64, 5_764_801, 20_201_227)?, Some(8));
+        assert_eq!(discrete_log(2, 3, 7)?, None);
+        assert_eq!(discrete_log(3, 1, 7)?, Some(0));
+        Ok(())
+    }
+}
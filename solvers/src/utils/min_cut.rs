@@ -0,0 +1,133 @@
+use std::collections::BinaryHeap;
+
+use common::hash::prelude::*;
+use common::{ensure, Result};
+
+/// Edges to remove to split a graph in two, and the two sides of the graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cut {
+    pub size: usize,
+    pub sides: [Vec<usize>; 2],
+}
+
+/// Global minimum cut of an undirected multigraph with nodes `0..nb_nodes`,
+/// with the deterministic Stoer-Wagner algorithm.
+pub fn min_cut(nb_nodes: usize, edges: &[[usize; 2]]) -> Result<Cut> {
+    ensure!(
+        nb_nodes >= 2,
+        "At least two nodes are needed to cut a graph"
+    );
+    let mut weights: Vec<HashMap<usize, usize>> = vec![HashMap::new(); nb_nodes];
+    for &[a, b] in edges {
+        ensure!(a < nb_nodes && b < nb_nodes, "Unknown node in {:?}", [a, b]);
+        if a != b {
+            *weights[a].entry(b).or_default() += 1;
+            *weights[b].entry(a).or_default() += 1;
+        }
+    }
+    // Nodes merged into each remaining node.
+    let mut groups: Vec<Option<Vec<usize>>> = (0..nb_nodes).map(|u| Some(vec![u])).collect();
+    let mut best: Option<(usize, Vec<usize>)> = None;
+    let mut connectivity = vec![0; nb_nodes];
+    let mut added = vec![false; nb_nodes];
+    for nb_remaining in (2..=nb_nodes).rev() {
+        // Minimum cut phase: add the most tightly connected node until there is none left.
+        let start = groups.iter().position(Option::is_some).unwrap_or_default();
+        for (u, group) in groups.iter().enumerate() {
+            connectivity[u] = 0;
+            added[u] = group.is_none();
+        }
+        let mut heap = BinaryHeap::from([(0, start)]);
+        let (mut s, mut t) = (start, start);
+        let mut nb_added = 0;
+        while let Some((w, u)) = heap.pop() {
+            if added[u] || w != connectivity[u] {
+                continue;
+            }
+            added[u] = true;
+            nb_added += 1;
+            (s, t) = (t, u);
+            if nb_added == nb_remaining {
+                break;
+            }
+            for (&v, &w) in &weights[u] {
+                if !added[v] {
+                    connectivity[v] += w;
+                    heap.push((connectivity[v], v));
+                }
+            }
+        }
+        if nb_added < nb_remaining {
+            // Disconnected graph: what was added is a side with no edge to the rest.
+            let side = (0..nb_nodes)
+                .filter(|&u| added[u] && groups[u].is_some())
+                .flat_map(|u| groups[u].iter().flatten().copied())
+                .collect();
+            best = Some((0, side));
+            break;
+        }
+        // The cut of the phase separates `t` from the rest.
+        let cut_of_phase = connectivity[t];
+        if best.as_ref().map_or(true, |(size, _)| cut_of_phase < *size) {
+            best = Some((cut_of_phase, groups[t].clone().unwrap_or_default()));
+        }
+        // Merge `t` into `s`.
+        let t_weights = std::mem::take(&mut weights[t]);
+        for (v, w) in t_weights {
+            weights[v].remove(&t);
+            if v != s {
+                *weights[s].entry(v).or_default() += w;
+                *weights[v].entry(s).or_default() += w;
+            }
+        }
+        let t_group = groups[t].take().unwrap_or_default();
+        groups[s].get_or_insert_with(Vec::new).extend(t_group);
+    }
+    let (size, mut side) = best.unwrap_or_default();
+    side.sort_unstable();
+    let mut in_side = vec![false; nb_nodes];
+    for &u in &side {
+        in_side[u] = true;
+    }
+    let other = (0..nb_nodes).filter(|&u| !in_side[u]).collect();
+    Ok(Cut {
+        size,
+        sides: [side, other],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore]
+    fn test_min_cut() -> Result<()> {
+        // Two squares with diagonals linked by two edges.
+        let edges = [
+            [0, 1],
+            [1, 2],
+            [2, 3],
+            [3, 0],
+            [0, 2],
+            [1, 3],
+            [4, 5],
+            [5, 6],
+            [6, 7],
+            [7, 4],
+            [4, 6],
+            [5, 7],
+            [0, 4],
+            [2, 6],
+        ];
+        let cut = min_cut(8, &edges)?;
+        assert_eq!(cut.size, 2);
+        let mut sides = cut.sides;
+        sides.sort();
+        assert_eq!(sides, [vec![0, 1, 2, 3], vec![4, 5, 6, 7]]);
+        assert_eq!(min_cut(4, &[[0, 1], [2, 3]])?.size, 0);
+        assert!(min_cut(1, &[]).is_err());
+        assert!(min_cut(2, &[[0, 2]]).is_err());
+        Ok(())
+    }
+}
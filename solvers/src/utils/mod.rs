@@ -1,15 +1,37 @@
+mod branch_bound;
+mod cycle;
 mod digit;
+mod dir;
 mod grid;
 mod heuristic_item;
+mod interval;
+mod linear;
+mod math;
+mod min_cut;
+mod ocr;
 mod ok_iterator;
 mod permutations;
+mod point;
+mod search;
 mod slice;
 mod u64ascii;
 
+pub use branch_bound::maximize;
+pub use cycle::{brent as cycle_brent, detect as cycle_detect, floyd as cycle_floyd, Cycle};
 pub use digit::{char10, char16};
-pub use grid::{neighbors, parse as parse_to_grid, parse_with_loc as parse_to_grid_with_loc};
+pub use dir::{Dir4, Dir8};
+pub use grid::{neighbors, parse as parse_to_grid, parse_with_loc as parse_to_grid_with_loc, Grid};
 pub use heuristic_item::HeuristicItem;
+pub use interval::{Cuboid, Interval, IntervalSet};
+pub use linear::{solve as solve_linear, solve_integral as solve_linear_integral, Rational};
+pub use math::{crt, discrete_log, ext_gcd, mod_inv, mod_pow};
+pub use min_cut::{min_cut, Cut};
+pub use ocr::{answer as ocr_answer, read as ocr, read_points as ocr_points};
 pub use ok_iterator::OkIterator;
 pub use permutations::map as permutations_map;
+pub use point::{Point2, Point3};
+pub use search::{
+    bfs, bfs_with, flood_fill, flood_fill_with, DenseMap, Explored, Node, Search, StateMap,
+};
 pub use slice::Extension as SliceExt;
 pub use u64ascii::U64Ascii;
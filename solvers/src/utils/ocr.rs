@@ -0,0 +1,187 @@
+use itertools::Itertools;
+
+use common::{bail, format_err, Answer, Result};
+
+type Font = &'static [(char, &'static [&'static str])];
+
+/// Letters 6 pixels high, mostly 4 pixels wide, known from 2016 to 2022.
+const FONT_4X6: Font = &[
+    ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', &[".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Letters 10 pixels high and 6 pixels wide, known from 2018.
+#[rustfmt::skip]
+const FONT_6X10: Font = &[
+    ('A', &["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', &["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', &[".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', &[".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', &["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', &["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', &["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', &["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', &["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', &["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', &["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', &["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', &["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+/// Read the capital letters drawn by lit pixels, row by row.
+///
+/// Empty rows around the letters are ignored and letters are separated by empty columns.
+pub fn read(grid: &[Vec<bool>]) -> Result<String> {
+    let Some(top) = grid.iter().position(|row| row.contains(&true)) else {
+        bail!("No letter to read");
+    };
+    let bottom = grid
+        .iter()
+        .rposition(|row| row.contains(&true))
+        .unwrap_or(top);
+    let rows = &grid[top..=bottom];
+    let font = match rows.len() {
+        6 => FONT_4X6,
+        10 => FONT_6X10,
+        height => bail!("No font for letters {} pixels high", height),
+    };
+    let width = rows.iter().map(Vec::len).max().unwrap_or_default();
+    let lit = |r: usize, c: usize| rows[r].get(c).copied().unwrap_or_default();
+    let lit_columns = (0..width).map(|c| (0..rows.len()).any(|r| lit(r, c)));
+    lit_columns
+        .enumerate()
+        .chunk_by(|(_, any_lit)| *any_lit)
+        .into_iter()
+        .filter(|(any_lit, _)| *any_lit)
+        .map(|(_, columns)| {
+            let columns = columns.map(|(c, _)| c).collect_vec();
+            let glyph = (0..rows.len())
+                .map(|r| {
+                    columns
+                        .iter()
+                        .map(|&c| if lit(r, c) { '#' } else { '.' })
+                        .join("")
+                })
+                .collect_vec();
+            font.iter()
+                .find(|(_, pixels)| trimmed(pixels) == glyph)
+                .map(|(letter, _)| *letter)
+                .ok_or_else(|| format_err!("Unknown letter:\n{}", glyph.join("\n")))
+        })
+        .collect()
+}
+
+/// Read the capital letters drawn by lit pixels at the given `(row, column)` locations.
+pub fn read_points<I>(points: I) -> Result<String>
+where
+    I: IntoIterator<Item = (usize, usize)>,
+{
+    let points = points.into_iter().collect_vec();
+    let (Some(r0), Some(c0)) = (
+        points.iter().map(|(r, _)| *r).min(),
+        points.iter().map(|(_, c)| *c).min(),
+    ) else {
+        bail!("No letter to read");
+    };
+    let nrows = points
+        .iter()
+        .map(|(r, _)| r - r0 + 1)
+        .max()
+        .unwrap_or_default();
+    let ncols = points
+        .iter()
+        .map(|(_, c)| c - c0 + 1)
+        .max()
+        .unwrap_or_default();
+    let mut grid = vec![vec![false; ncols]; nrows];
+    for (r, c) in points {
+        grid[r - r0][c - c0] = true;
+    }
+    read(&grid)
+}
+
+/// The letters when they can be read, the drawing otherwise.
+#[must_use]
+pub fn answer(grid: Vec<Vec<bool>>) -> Answer {
+    read(&grid).map_or(Answer::Letters(grid), Answer::Text)
+}
+
+/// Glyph without its empty columns.
+fn trimmed(pixels: &[&str]) -> Vec<String> {
+    let width = pixels.first().map_or(0, |row| row.len());
+    let columns = (0..width)
+        .filter(|&c| pixels.iter().any(|row| row.as_bytes()[c] == b'#'))
+        .collect_vec();
+    pixels
+        .iter()
+        .map(|row| {
+            columns
+                .iter()
+                .map(|&c| char::from(row.as_bytes()[c]))
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drawing(s: &str) -> Vec<Vec<bool>> {
+        s.lines()
+            .map(|line| line.chars().map(|ch| ch == '#').collect())
+            .collect()
+    }
+
+    #[test]
+    #[ignore]
+    fn test_read() -> Result<()> {
+        let small = drawing(
+            "\
+###..#..#..##..#..#.#..#.###..####.#..#.
+#..#.#..#.#..#.#.#..#..#.#..#.#....#.#..
+#..#.#..#.#..#.##...####.###..###..##...
+###..#..#.####.#.#..#..#.#..#.#....#.#..
+#.#..#..#.#..#.#.#..#..#.#..#.#....#.#..
+#..#..##..#..#.#..#.#..#.###..####.#..#.",
+        );
+        assert_eq!(read(&small)?, "RUAKHBEK");
+        let big = drawing(
+            "\
+#....#..######
+#....#..#.....
+.#..#...#.....
+.#..#...#.....
+..##....#####.
+..##....#.....
+.#..#...#.....
+.#..#...#.....
+#....#..#.....
+#....#..######
+..............",
+        );
+        assert_eq!(read(&big)?, "XE");
+        let points = [(0, 4), (0, 5), (1, 3), (1, 6), (2, 3), (2, 6)];
+        assert!(read_points(points).is_err());
+        assert!(read(&[]).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// Point of a plane, or location in a grid where `x` is the column and `y` the row.
+///
+/// On a grid, `y` grows downwards, like rows do.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// Point in space.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// `|a - b|` without overflowing unsigned integers.
+#[inline]
+fn abs_diff<T: Copy + Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a < b {
+        b - a
+    } else {
+        a - b
+    }
+}
+
+impl<T> Point2<T> {
+    #[inline]
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// From a `(row, column)` location, as used by `Grid`.
+    #[inline]
+    pub fn from_loc((r, c): (T, T)) -> Self {
+        Self { x: c, y: r }
+    }
+
+    /// The `(row, column)` location, as used by `Grid`.
+    #[inline]
+    pub fn loc(self) -> (T, T) {
+        (self.y, self.x)
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point2<T> {
+    /// Taxicab distance.
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// Chessboard distance.
+    #[must_use]
+    pub fn chebyshev(self, other: Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+}
+
+impl Point2<usize> {
+    /// Move by a signed offset, `None` on underflow or overflow.
+    #[must_use]
+    #[inline]
+    pub const fn checked_add_signed(self, offset: Point2<isize>) -> Option<Self> {
+        match (
+            self.x.checked_add_signed(offset.x),
+            self.y.checked_add_signed(offset.y),
+        ) {
+            (Some(x), Some(y)) => Some(Self { x, y }),
+            _ => None,
+        }
+    }
+
+    /// Is it inside a `width` x `height` grid?
+    #[must_use]
+    #[inline]
+    pub const fn in_bounds(self, width: usize, height: usize) -> bool {
+        self.x < width && self.y < height
+    }
+}
+
+impl<T> Point3<T> {
+    #[inline]
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point3<T> {
+    /// Taxicab distance.
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+
+    /// Chessboard distance.
+    #[must_use]
+    pub fn chebyshev(self, other: Self) -> T {
+        abs_diff(self.x, other.x)
+            .max(abs_diff(self.y, other.y))
+            .max(abs_diff(self.z, other.z))
+    }
+}
+
+macro_rules! impl_ops {
+    ($point:ident: $($field:ident)+) => {
+        impl<T> From<[T; count_exprs!($($field,)+)]> for $point<T> {
+            #[inline]
+            fn from([$($field),+]: [T; count_exprs!($($field,)+)]) -> Self {
+                Self { $($field),+ }
+            }
+        }
+
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, other: Self) -> Self {
+                Self { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, other: Self) -> Self {
+                Self { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        /// Scale by a factor.
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, factor: T) -> Self {
+                Self { $($field: self.$field * factor),+ }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $point<T> {
+            #[inline]
+            fn add_assign(&mut self, other: Self) {
+                $(self.$field += other.$field;)+
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $point<T> {
+            #[inline]
+            fn sub_assign(&mut self, other: Self) {
+                $(self.$field -= other.$field;)+
+            }
+        }
+    };
+}
+
+impl_ops!(Point2: x y);
+impl_ops!(Point3: x y z);
@@ -0,0 +1,502 @@
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::ops::Add;
+
+use crate::utils::HeuristicItem;
+use common::hash::prelude::*;
+
+/// What a search knows about a reached state.
+#[derive(Debug, Clone)]
+pub struct Node<S, C> {
+    /// Cost of the cheapest way from a start to this state.
+    pub cost: C,
+    /// Previous state on a cheapest way, `None` for starts.
+    parent: Option<S>,
+}
+
+/// How a search remembers reached states.
+pub trait StateMap<S, V> {
+    fn get(&self, state: &S) -> Option<&V>;
+    fn get_mut(&mut self, state: &S) -> Option<&mut V>;
+    fn insert(&mut self, state: S, value: V);
+    /// The number of reached states.
+    fn len(&self) -> usize;
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<S: Hash + Eq, V> StateMap<S, V> for HashMap<S, V> {
+    #[inline]
+    fn get(&self, state: &S) -> Option<&V> {
+        Self::get(self, state)
+    }
+
+    #[inline]
+    fn get_mut(&mut self, state: &S) -> Option<&mut V> {
+        Self::get_mut(self, state)
+    }
+
+    #[inline]
+    fn insert(&mut self, state: S, value: V) {
+        Self::insert(self, state, value);
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        Self::len(self)
+    }
+}
+
+/// States indexed in `0..capacity`, stored in a vector instead of being hashed.
+#[derive(Debug, Clone)]
+pub struct DenseMap<S, V, F> {
+    values: Vec<Option<V>>,
+    index: F,
+    len: usize,
+    _marker: PhantomData<S>,
+}
+
+impl<S, V, F: Fn(&S) -> usize> DenseMap<S, V, F> {
+    pub fn new(capacity: usize, index: F) -> Self {
+        Self {
+            values: std::iter::repeat_with(|| None).take(capacity).collect(),
+            index,
+            len: 0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S, V, F: Fn(&S) -> usize> StateMap<S, V> for DenseMap<S, V, F> {
+    #[inline]
+    fn get(&self, state: &S) -> Option<&V> {
+        self.values[(self.index)(state)].as_ref()
+    }
+
+    #[inline]
+    fn get_mut(&mut self, state: &S) -> Option<&mut V> {
+        self.values[(self.index)(state)].as_mut()
+    }
+
+    #[inline]
+    fn insert(&mut self, state: S, value: V) {
+        let old = self.values[(self.index)(&state)].replace(value);
+        if old.is_none() {
+            self.len += 1;
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+/// Costs of moves, added from zero (the default value).
+pub trait Cost: Copy + Ord + Default + Add<Output = Self> {}
+
+impl<C: Copy + Ord + Default + Add<Output = Self>> Cost for C {}
+
+type NoHeuristic<S, C> = fn(&S) -> C;
+
+/// Dijkstra's algorithm, or A* when given a heuristic.
+///
+/// ```ignore
+/// let explored = Search::new(|&n: &u32| [(n + 1, 1), (2 * n, 3)])
+///     .heuristic(|&n| (100 - n.min(100)) / 2) // Never more than the real cost!
+///     .run([1], |&n| n == 100);
+/// assert_eq!(explored.cost(), Some(...));
+/// ```
+pub struct Search<S, C, FS, FH, M> {
+    successors: FS,
+    heuristic: FH,
+    visited: M,
+    all_paths: bool,
+    _marker: PhantomData<(S, C)>,
+}
+
+impl<S, C, FS, I> Search<S, C, FS, NoHeuristic<S, C>, HashMap<S, Node<S, C>>>
+where
+    S: Hash + Eq,
+    C: Cost,
+    FS: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+{
+    /// Search with the given successors of a state and the costs to move to them.
+    pub fn new(successors: FS) -> Self {
+        Self {
+            successors,
+            heuristic: |_| C::default(),
+            visited: HashMap::new(),
+            all_paths: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S, C, FS, FH, M, I> Search<S, C, FS, FH, M>
+where
+    S: Clone,
+    C: Cost,
+    FS: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    FH: FnMut(&S) -> C,
+    M: StateMap<S, Node<S, C>>,
+{
+    /// Lower bound of the cost from a state to the goal, it makes it an A* search.
+    ///
+    /// It must never overestimate, and neither decrease more than a move costs.
+    pub fn heuristic<FH2>(self, heuristic: FH2) -> Search<S, C, FS, FH2, M>
+    where
+        FH2: FnMut(&S) -> C,
+    {
+        Search {
+            successors: self.successors,
+            heuristic,
+            visited: self.visited,
+            all_paths: self.all_paths,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Remember reached states in another way, like a `DenseMap`.
+    pub fn visited<M2>(self, visited: M2) -> Search<S, C, FS, FH, M2>
+    where
+        M2: StateMap<S, Node<S, C>>,
+    {
+        Search {
+            successors: self.successors,
+            heuristic: self.heuristic,
+            visited,
+            all_paths: self.all_paths,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Keep track of all cheapest ways instead of one, to get all shortest paths.
+    #[must_use]
+    pub const fn all_paths(mut self) -> Self {
+        self.all_paths = true;
+        self
+    }
+
+    /// Search from the starts until a goal is reached, or until there is nothing left to explore.
+    pub fn run<It, FG>(mut self, starts: It, mut is_goal: FG) -> Explored<S, C, M>
+    where
+        It: IntoIterator<Item = S>,
+        FG: FnMut(&S) -> bool,
+    {
+        let mut frontier = BinaryHeap::new();
+        for start in starts {
+            let node = Node {
+                cost: C::default(),
+                parent: None,
+            };
+            self.visited.insert(start.clone(), node);
+            frontier.push(HeuristicItem::rev(
+                (self.heuristic)(&start),
+                (C::default(), start),
+            ));
+        }
+        let mut goal: Option<(S, C)> = None;
+        let mut other_parents = vec![];
+        while let Some(HeuristicItem { heuristic, item }) = frontier.pop() {
+            let (cost, state) = item;
+            if let Some((_, goal_cost)) = &goal {
+                // Only there when all paths are requested.
+                if heuristic.0 > *goal_cost {
+                    break;
+                }
+            }
+            if self
+                .visited
+                .get(&state)
+                .is_some_and(|node| node.cost < cost)
+            {
+                continue; // Outdated.
+            }
+            if goal.is_none() && is_goal(&state) {
+                goal = Some((state.clone(), cost));
+                if !self.all_paths {
+                    break;
+                }
+            }
+            for (next, step_cost) in (self.successors)(&state) {
+                let next_cost = cost + step_cost;
+                match self.visited.get_mut(&next) {
+                    Some(node) if node.cost < next_cost => continue,
+                    Some(node) if node.cost == next_cost => {
+                        // Each state is explored once, it's not already a parent.
+                        if self.all_paths {
+                            other_parents.push((next, state.clone(), next_cost));
+                        }
+                        continue;
+                    }
+                    _ => {}
+                }
+                let node = Node {
+                    cost: next_cost,
+                    parent: Some(state.clone()),
+                };
+                let estimation = next_cost + (self.heuristic)(&next);
+                self.visited.insert(next.clone(), node);
+                frontier.push(HeuristicItem::rev(estimation, (next_cost, next)));
+            }
+        }
+        Explored {
+            visited: self.visited,
+            goal,
+            other_parents,
+        }
+    }
+}
+
+/// Breadth-first search from the starts until a goal is reached, each move costs 1.
+pub fn bfs<S, It, FN, I, FG>(
+    starts: It,
+    neighbors: FN,
+    is_goal: FG,
+) -> Explored<S, usize, HashMap<S, Node<S, usize>>>
+where
+    S: Clone + Hash + Eq,
+    It: IntoIterator<Item = S>,
+    FN: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+    FG: FnMut(&S) -> bool,
+{
+    bfs_with(HashMap::new(), starts, neighbors, is_goal)
+}
+
+/// Breadth-first search remembering reached states in the given way.
+pub fn bfs_with<S, M, It, FN, I, FG>(
+    mut visited: M,
+    starts: It,
+    mut neighbors: FN,
+    mut is_goal: FG,
+) -> Explored<S, usize, M>
+where
+    S: Clone,
+    M: StateMap<S, Node<S, usize>>,
+    It: IntoIterator<Item = S>,
+    FN: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+    FG: FnMut(&S) -> bool,
+{
+    let mut queue = VecDeque::new();
+    for start in starts {
+        let node = Node {
+            cost: 0,
+            parent: None,
+        };
+        visited.insert(start.clone(), node);
+        queue.push_back((0, start));
+    }
+    while let Some((dist, state)) = queue.pop_front() {
+        if is_goal(&state) {
+            return Explored {
+                visited,
+                goal: Some((state, dist)),
+                other_parents: vec![],
+            };
+        }
+        for next in neighbors(&state) {
+            if visited.get(&next).is_none() {
+                let node = Node {
+                    cost: dist + 1,
+                    parent: Some(state.clone()),
+                };
+                visited.insert(next.clone(), node);
+                queue.push_back((dist + 1, next));
+            }
+        }
+    }
+    Explored {
+        visited,
+        goal: None,
+        other_parents: vec![],
+    }
+}
+
+/// Reach everything reachable from the starts, with distances.
+pub fn flood_fill<S, It, FN, I>(
+    starts: It,
+    neighbors: FN,
+) -> Explored<S, usize, HashMap<S, Node<S, usize>>>
+where
+    S: Clone + Hash + Eq,
+    It: IntoIterator<Item = S>,
+    FN: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    bfs(starts, neighbors, |_| false)
+}
+
+/// Reach everything reachable from the starts, remembering reached states in the given way.
+pub fn flood_fill_with<S, M, It, FN, I>(
+    visited: M,
+    starts: It,
+    neighbors: FN,
+) -> Explored<S, usize, M>
+where
+    S: Clone,
+    M: StateMap<S, Node<S, usize>>,
+    It: IntoIterator<Item = S>,
+    FN: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    bfs_with(visited, starts, neighbors, |_| false)
+}
+
+/// The outcome of a search.
+#[derive(Debug, Clone)]
+pub struct Explored<S, C, M> {
+    visited: M,
+    goal: Option<(S, C)>,
+    /// `(state, parent, cost)` on cheap ways, other than the parents of nodes.
+    /// Only the ones with the final cost of the state are on cheapest ways.
+    other_parents: Vec<(S, S, C)>,
+}
+
+impl<S: Clone, C: Copy, M: StateMap<S, Node<S, C>>> Explored<S, C, M> {
+    /// The reached goal.
+    #[must_use]
+    pub fn goal(&self) -> Option<&S> {
+        self.goal.as_ref().map(|(state, _)| state)
+    }
+
+    /// The cost to the reached goal.
+    #[must_use]
+    pub fn cost(&self) -> Option<C> {
+        self.goal.as_ref().map(|(_, cost)| *cost)
+    }
+
+    /// The cost to a reached state, it's the lowest one for states fully explored.
+    #[must_use]
+    pub fn cost_to(&self, state: &S) -> Option<C> {
+        self.visited.get(state).map(|node| node.cost)
+    }
+
+    /// The number of reached states.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.visited.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// How reached states were remembered.
+    #[must_use]
+    pub fn into_visited(self) -> M {
+        self.visited
+    }
+
+    /// A cheapest path from a start to the goal, both included.
+    #[must_use]
+    pub fn path(&self) -> Option<Vec<S>> {
+        let mut state = self.goal()?.clone();
+        let mut path = vec![state.clone()];
+        while let Some(parent) = self
+            .visited
+            .get(&state)
+            .and_then(|node| node.parent.clone())
+        {
+            path.push(parent.clone());
+            state = parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl<S, C, M> Explored<S, C, M>
+where
+    S: Clone + Hash + Eq,
+    C: Copy + PartialEq,
+    M: StateMap<S, Node<S, C>>,
+{
+    /// All cheapest paths from a start to the goal, when the search tracked them all.
+    #[must_use]
+    pub fn all_paths(&self) -> Vec<Vec<S>> {
+        let Some(goal) = self.goal() else {
+            return vec![];
+        };
+        let mut others: HashMap<&S, Vec<&S>> = HashMap::new();
+        for (state, parent, cost) in &self.other_parents {
+            if self.cost_to(state) == Some(*cost) {
+                others.entry(state).or_default().push(parent);
+            }
+        }
+        let mut paths = vec![];
+        let mut stack = vec![vec![goal]];
+        while let Some(rev_path) = stack.pop() {
+            let Some(&state) = rev_path.last() else {
+                continue;
+            };
+            let Some(parent) = self
+                .visited
+                .get(state)
+                .and_then(|node| node.parent.as_ref())
+            else {
+                paths.push(rev_path.into_iter().rev().cloned().collect());
+                continue;
+            };
+            let parents = others.get(state).into_iter().flatten().copied();
+            for parent in std::iter::once(parent).chain(parents) {
+                let mut new_path = rev_path.clone();
+                new_path.push(parent);
+                stack.push(new_path);
+            }
+        }
+        paths
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore]
+    fn test_search() {
+        // Grid without walls: many shortest paths.
+        let neighbors = |&(x, y): &(u8, u8)| {
+            [(x + 1, y), (x, y + 1)]
+                .into_iter()
+                .filter(|&(x, y)| x <= 2 && y <= 2)
+                .map(|p| (p, 1))
+        };
+        let explored = Search::new(neighbors).run([(0, 0)], |p| p == &(2, 2));
+        assert_eq!(explored.cost(), Some(4));
+        assert_eq!(explored.path().map(|path| path.len()), Some(5));
+        let explored = Search::new(neighbors)
+            .heuristic(|&(x, y)| 4 - x - y)
+            .visited(DenseMap::new(9, |&(x, y)| usize::from(3 * y + x)))
+            .all_paths()
+            .run([(0, 0)], |p| p == &(2, 2));
+        assert_eq!(explored.cost(), Some(4));
+        assert_eq!(explored.all_paths().len(), 6);
+        // 4 is reached at cost 5 from 1 and 2 before being reached at cost 4 from 3.
+        let edges = |&n: &u8| match n {
+            0 => vec![(1, 1), (2, 1), (3, 2)],
+            1 | 2 => vec![(4, 4)],
+            3 => vec![(4, 2)],
+            _ => vec![],
+        };
+        let explored = Search::new(edges).all_paths().run([0], |&n| n == 4);
+        assert_eq!(explored.cost(), Some(4));
+        assert_eq!(explored.all_paths(), [[0, 3, 4]]);
+        let explored = bfs([0_i32], |&n| [n - 3, n + 5], |&n| n == 1);
+        assert_eq!(explored.cost(), Some(5)); // +5 +5 -3 -3 -3
+        let explored = flood_fill([0_u8], |&n| [n / 2, n.saturating_mul(2) % 10]);
+        assert_eq!(explored.len(), 1);
+        let explored = flood_fill([3_u8], |&n| [n / 2, n.saturating_mul(2) % 10]);
+        assert_eq!(explored.len(), 7); // 0 1 2 3 4 6 8
+    }
+}
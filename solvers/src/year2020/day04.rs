@@ -44,7 +44,7 @@ fn range_check(mut s: &str, mini: i32, maxi: i32, suffix: Option<&str>) -> bool
         };
         s = s2;
     };
-    s.parse().map_or(false, |nb| mini <= nb && nb <= maxi)
+    s.parse().is_ok_and(|nb| mini <= nb && nb <= maxi)
 }
 
 impl<'a> Passport<'a> {
@@ -77,7 +77,7 @@ impl<'a> Passport<'a> {
             && range_check(expiration, 2020, 2030, None)
             && (range_check(height, 150, 193, Some("cm"))
                 || range_check(height, 59, 76, Some("in")))
-            && hair.strip_prefix('#').map_or(false, |hexes| {
+            && hair.strip_prefix('#').is_some_and(|hexes| {
                 hexes.len() == 6 && hexes.chars().all(|ch| matches!(ch, '0'..='9' | 'a'..='f'))
             })
             && ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(eye)
@@ -1,5 +1,5 @@
 use common::prelude::*;
-use crate::utils::OkIterator;
+use crate::utils::{crt, OkIterator};
 
 /// Shuttle Search
 pub fn solver(part: Part, input: &str) -> Result<i64> {
@@ -28,34 +28,14 @@ pub fn solver(part: Part, input: &str) -> Result<i64> {
                 .enumerate()
                 .filter_map(|(idx, bus_id)| bus_id.map(|bus_id| (idx as i64, bus_id)))
                 .collect();
-            let n = modular_equation.iter().map(|(_, m)| m).product();
             // for (k, m) in modular_equation: unknown_result + k == 0 (modulo m)
             #[cfg(debug_assertions)]
-            println!("{modular_equation:?} mod {n}");
-            modular_equation
-                .iter()
-                .map(|(k, m)| {
-                    let n0 = n / m;
-                    mod_inv(n0, *m)
-                        .map(|n0_inv| n0 * n0_inv * -k)
-                        .with_context(|| format_err!("{} has no inverse mod {}", n0, m))
-                })
-                .ok_sum()
-                .map(|res: i64| res.rem_euclid(n))
+            println!("{modular_equation:?}");
+            crt(modular_equation.into_iter().map(|(k, m)| (-k, m))).map(|(res, _)| res)
         }
     }
 }
 
-fn mod_inv(a: i64, n: i64) -> Option<i64> {
-    let mut t = (0, 1);
-    let mut r = (n, a);
-    while r.1 != 0 {
-        t = (t.1, t.0 - r.0.div_euclid(r.1) * t.1);
-        r = (r.1, r.0.rem_euclid(r.1));
-    }
-    (r.0 <= 1).then_some(t.0)
-}
-
 test_solver! {
     "939\n7,13,x,x,59,x,31,19" => (295, 1068781),
     "0\n17,x,13,19" => ((), 3417),
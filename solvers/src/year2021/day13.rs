@@ -1,10 +1,10 @@
 use itertools::Itertools;
 
 use common::{prelude::*, Ok};
-use crate::utils::OkIterator;
+use crate::utils::{ocr_answer, OkIterator};
 
 /// Transparent Origami
-pub fn solver(part: Part, input: &str) -> Result<String> {
+pub fn solver(part: Part, input: &str) -> Result<Answer> {
     let (coords, fold_alongs) = input
         .split_once("\n\n")
         .context("No empty line after coords")?;
@@ -50,7 +50,7 @@ pub fn solver(part: Part, input: &str) -> Result<String> {
             .collect();
     }
     Ok(match part {
-        Part1 => coords.len().to_string(),
+        Part1 => coords.len().into(),
         Part2 => {
             let (xs, ys): (Vec<_>, Vec<_>) = coords.into_iter().unzip();
             let (&x0, &x1) = xs
@@ -63,11 +63,11 @@ pub fn solver(part: Part, input: &str) -> Result<String> {
                 .minmax()
                 .into_option()
                 .context("No point left?!")?;
-            let mut grid = vec![vec!['░'; (x1 - x0 + 1) as usize]; (y1 - y0 + 1) as usize];
-            for (x, y) in xs.into_iter().zip(ys.into_iter()) {
-                grid[(y - y0) as usize][(x - x0) as usize] = '█';
+            let mut grid = vec![vec![false; (x1 - x0 + 1) as usize]; (y1 - y0 + 1) as usize];
+            for (x, y) in xs.into_iter().zip(ys) {
+                grid[(y - y0) as usize][(x - x0) as usize] = true;
             }
-            grid.into_iter().map(|line| line.iter().join("")).join("\n")
+            ocr_answer(grid)
         }
     })
 }
@@ -101,11 +101,5 @@ fold along x=5
 █░░░█
 █░░░█
 █████"), // Squared O
-    include_input!(21 13) => ("607", "\
-░██░░███░░████░█░░░░███░░████░████░█░░░
-█░░█░█░░█░░░░█░█░░░░█░░█░█░░░░░░░█░█░░░
-█░░░░█░░█░░░█░░█░░░░█░░█░███░░░░█░░█░░░
-█░░░░███░░░█░░░█░░░░███░░█░░░░░█░░░█░░░
-█░░█░█░░░░█░░░░█░░░░█░░░░█░░░░█░░░░█░░░
-░██░░█░░░░████░████░█░░░░█░░░░████░████"), // CPZLPFZL
+    include_input!(21 13) => ("607", "CPZLPFZL"),
 }
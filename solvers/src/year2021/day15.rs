@@ -1,9 +1,7 @@
-use std::collections::BinaryHeap;
-
 use itertools::iproduct;
 
 use common::prelude::*;
-use crate::utils::{char10, neighbors, parse_to_grid, HeuristicItem};
+use crate::utils::{char10, parse_to_grid, DenseMap, Grid, Search};
 
 /// Chiton
 pub fn solver(part: Part, input: &str) -> Result<u32> {
@@ -33,29 +31,16 @@ pub fn solver(part: Part, input: &str) -> Result<u32> {
         }
         (grid, nrows, ncols)
     }; // now immutables
-    let mut risks = vec![vec![None; ncols]; nrows];
-    risks[0][0] = Some(0);
-    let mut heap = BinaryHeap::from([HeuristicItem::rev(0, (0, 0))]);
+    let grid = Grid::from_rows(grid)?;
     let end = (nrows - 1, ncols - 1);
     // Minimize the risk to the end.
-    while let Some(HeuristicItem { item: loc, .. }) = heap.pop() {
-        if loc == end {
-            break; // early exit
-        }
-        for (r, c) in neighbors(loc, nrows, ncols, false) {
-            let new_risk = grid[r][c]
-                + risks[loc.0][loc.1].context("The risk should exist for elements of the heap")?;
-            if !matches!(risks[r][c], Some(old_risk) if old_risk <= new_risk) {
-                risks[r][c].replace(new_risk);
-                heap.push(HeuristicItem::rev(
-                    // The manhattan distance to the end is a lower bound of the remaining risk.
-                    new_risk as usize + end.0 - r + end.1 - c,
-                    (r, c),
-                ));
-            }
-        }
-    }
-    risks[nrows - 1][ncols - 1].context("Did not reach the end")
+    Search::new(|&loc| grid.neighbors(loc, false).into_iter().map(|p| (p, grid[p])))
+        // The manhattan distance to the end is a lower bound of the remaining risk.
+        .heuristic(|&(r, c)| u32::try_from(end.0 - r + end.1 - c).unwrap_or(u32::MAX))
+        .visited(DenseMap::new(nrows * ncols, |&(r, c)| r * ncols + c))
+        .run([(0, 0)], |loc| loc == &end)
+        .cost()
+        .context("Did not reach the end")
 }
 
 test_solver! {
@@ -1,115 +1,17 @@
-use std::{
-    cmp::{max, min},
-    str::FromStr,
-};
-
-use itertools::{iproduct, Itertools};
+use itertools::Itertools;
 
 use common::prelude::*;
-use crate::utils::OkIterator;
-
-#[derive(Debug, PartialEq)]
-struct Cuboid {
-    x: Interval,
-    y: Interval,
-    z: Interval,
-}
-
-const SMALL50: Cuboid = Cuboid {
-    x: Interval(-50, 51),
-    y: Interval(-50, 51),
-    z: Interval(-50, 51),
-};
-
-#[derive(Debug, Clone, PartialEq)]
-struct Interval(i32, i32);
-
-impl FromStr for Interval {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self> {
-        let (start, end) = s.split_once("..").context("wrong delimiter")?;
-        let mini = start.parse()?;
-        let maxi: i32 = end.parse()?;
-        Self::new(mini, maxi + 1).context("Empty interval")
-        // Note the +1 !!!
-    }
-}
-
-impl Interval {
-    fn new(start: i32, end: i32) -> Option<Self> {
-        (start < end).then_some(Self(start, end))
-    }
-
-    #[allow(clippy::cast_sign_loss)]
-    const fn len(&self) -> usize {
-        let diff = self.1 - self.0;
-        debug_assert!(diff > 0);
-        diff as usize
-    }
+use crate::utils::{Cuboid, Interval, OkIterator};
 
-    fn intersection(&self, other: &Self) -> Option<Self> {
-        if self.0 < other.1 && self.1 > other.0 {
-            Self::new(max(self.0, other.0), min(self.1, other.1))
-        } else {
-            None
-        }
-    }
+type Cuboid3 = Cuboid<i64, 3>;
 
-    fn divide(&self, other: &Self) -> Vec<Self> {
-        // self --- && other =======
-        // 1. ---           non-intersecting: other remains intact
-        //         =======    -->    =======
-        // 2. ---      overlapping: other is cut in 2
-        //     =======    -->    == & =====
-        // 3.   ---    strictly containing: other is cut in 3
-        //     =======    -->    = & === & ===
-        let mut pts = [Some(other.0), None, None, Some(other.1)];
-        for (idx, n) in [(1, self.0), (2, self.1)] {
-            if other.0 < n && n < other.1 {
-                pts[idx] = Some(n);
-            }
-        }
-        pts.into_iter()
-            .flatten()
-            .tuple_windows()
-            .filter_map(|(a, b)| Self::new(a, b))
-            .collect()
-    }
-}
-
-impl Cuboid {
-    const fn volume(&self) -> usize {
-        self.x.len() * self.y.len() * self.z.len()
-    }
+const SMALL50: Cuboid3 = Cuboid([Interval::new(-50, 51); 3]);
 
-    fn intersection(&self, other: &Self) -> Option<Self> {
-        Some(Self {
-            x: self.x.intersection(&other.x)?,
-            y: self.y.intersection(&other.y)?,
-            z: self.z.intersection(&other.z)?,
-        })
-    }
-
-    fn divide_into(&self, other: Self, data: &mut Vec<Self>) {
-        // The difference with the `Interval` method `divide` is that here,
-        // the intersection (named `xyz` if any) is discarded.
-        if let Some(xyz) = self.intersection(&other) {
-            data.extend(
-                iproduct!(
-                    self.x.divide(&other.x).into_iter(),
-                    self.y.divide(&other.y).into_iter(),
-                    self.z.divide(&other.z).into_iter()
-                )
-                .filter_map(|(x, y, z)| {
-                    let new = Self { x, y, z };
-                    (new != xyz).then_some(new)
-                }),
-            );
-        } else {
-            data.push(other);
-        }
-    }
+fn parse_interval(s: &str) -> Result<Interval<i64>> {
+    let (start, end) = s.split_once("..").context("wrong delimiter")?;
+    Interval::inclusive(start.parse()?, end.parse()?)
+        .non_empty()
+        .context("Empty interval")
 }
 
 /// Reactor Reboot
@@ -124,24 +26,21 @@ pub fn solver(part: Part, input: &str) -> Result<usize> {
                 _ => bail!("Not on and not off but {}", on),
             };
             let (x, y, z) = xyz.splitn(3, ',').collect_tuple().context("Not x,y,z")?;
-            let cuboid = Cuboid {
-                x: x.strip_prefix("x=").context("not x=")?.parse()?,
-                y: y.strip_prefix("y=").context("not y=")?.parse()?,
-                z: z.strip_prefix("z=").context("not z=")?.parse()?,
-            };
+            let cuboid = Cuboid([
+                parse_interval(x.strip_prefix("x=").context("not x=")?)?,
+                parse_interval(y.strip_prefix("y=").context("not y=")?)?,
+                parse_interval(z.strip_prefix("z=").context("not z=")?)?,
+            ]);
             Ok((on, cuboid))
         })
         .ok_collect_vec()?;
-    let mut cuboids = vec![];
+    let mut cuboids: Vec<Cuboid3> = vec![];
     for (on, new) in data {
-        // Divide all cuboids with `new`, retain only non-intersecting parts with it.
-        cuboids = {
-            let mut new_cuboids = Vec::with_capacity(8 * cuboids.len());
-            for cuboid in cuboids {
-                new.divide_into(cuboid, &mut new_cuboids);
-            }
-            new_cuboids
-        };
+        // Retain only the parts of all cuboids that do not intersect with `new`.
+        cuboids = cuboids
+            .iter()
+            .flat_map(|cuboid| cuboid.difference(&new))
+            .collect();
         if on {
             cuboids.push(new);
         }
@@ -149,18 +48,18 @@ pub fn solver(part: Part, input: &str) -> Result<usize> {
     if part.one() {
         // Retain cuboids in -50..=50, -50..=50, -50..=50
         cuboids.retain_mut(|old| {
-            SMALL50.intersection(old).map_or(false, |new| {
+            SMALL50.intersection(old).is_some_and(|new| {
                 *old = new;
                 true
             })
         });
     }
-    // The `divide` method should make all cuboid pairs non-intersecting.
+    // The `difference` method should make all cuboid pairs non-intersecting.
     debug_assert!(cuboids
         .iter()
         .tuple_combinations()
         .all(|(c, d)| c.intersection(d).is_none()));
-    Ok(cuboids.iter().map(Cuboid::volume).sum())
+    Ok(usize::try_from(cuboids.iter().map(Cuboid::volume).sum::<i64>())?)
 }
 
 test_solver! {
@@ -367,6 +367,7 @@ impl<const N: usize> FromStr for State<N> {
         ensure!(lines.next() == Some("#############"), "First walls");
         ensure!(lines.next() == Some("#...........#"), "Second hallway");
         let mut rooms = [[None; N]; 4];
+        #[allow(clippy::needless_range_loop)] // `rooms[i][j]` with `i` from another loop.
         for j in 0..N {
             let line = lines.next().context("Missing line")?;
             if j == 0 {
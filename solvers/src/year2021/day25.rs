@@ -10,9 +10,9 @@ enum SeaCucumberKind {
 }
 
 /// Sea Cucumber
-pub fn solver(part: Part, input: &str) -> Result<String> {
+pub fn solver(part: Part, input: &str) -> Result<Answer> {
     if part.two() {
-        return Ok(SUCCESS.to_owned());
+        return Ok(Answer::NoPart2);
     }
     let mut grid = parse_to_grid(input.lines(), |ch| {
         Ok(match ch {
@@ -64,7 +64,7 @@ pub fn solver(part: Part, input: &str) -> Result<String> {
             break;
         }
     }
-    Ok(steps.to_string())
+    Ok(steps.into())
 }
 
 fn display_grid(grid: &Vec<Vec<Option<SeaCucumberKind>>>) {
@@ -85,12 +85,6 @@ fn display_grid(grid: &Vec<Vec<Option<SeaCucumberKind>>>) {
     println!();
 }
 
-const SUCCESS: &str = "\
-The sleigh keys are detected directly under the submarine.
-And you remotely start the sleigh.
-You can now go back to the surface.
-It's Christmas! Congratulations!";
-
 test_solver! {
     "\
 v...>>.vv>
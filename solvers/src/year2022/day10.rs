@@ -1,4 +1,5 @@
 use common::prelude::*;
+use crate::utils::ocr_answer;
 
 #[derive(Debug)]
 struct Crt {
@@ -42,22 +43,14 @@ impl Crt {
     const fn is_done(&self) -> bool {
         self.cycle == 240
     }
-}
 
-impl std::fmt::Display for Crt {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in self.screen.chunks_exact(40) {
-            for visible in row {
-                write!(f, "{}", if *visible { '█' } else { '░' })?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+    fn rows(&self) -> Vec<Vec<bool>> {
+        self.screen.chunks_exact(40).map(<[_]>::to_vec).collect()
     }
 }
 
 /// Cathode-Ray Tube
-pub fn solver(part: Part, input: &str) -> Result<String> {
+pub fn solver(part: Part, input: &str) -> Result<Answer> {
     let mut crt = Crt::new();
     for line in input.lines() {
         if line == "noop" {
@@ -70,8 +63,8 @@ pub fn solver(part: Part, input: &str) -> Result<String> {
     }
     ensure!(crt.is_done(), "Not 240 cycles");
     Ok(match part {
-        Part1 => crt.signal_strength_sum().to_string(),
-        Part2 => crt.to_string(),
+        Part1 => crt.signal_strength_sum().into(),
+        Part2 => ocr_answer(crt.rows()),
     })
 }
 
@@ -229,14 +222,6 @@ noop
 ████░░░░████░░░░████░░░░████░░░░████░░░░
 █████░░░░░█████░░░░░█████░░░░░█████░░░░░
 ██████░░░░░░██████░░░░░░██████░░░░░░████
-███████░░░░░░░███████░░░░░░░███████░░░░░
-"),
-    include_input!(22 10) => ("13220", "\
-███░░█░░█░░██░░█░░█░█░░█░███░░████░█░░█░
-█░░█░█░░█░█░░█░█░█░░█░░█░█░░█░█░░░░█░█░░
-█░░█░█░░█░█░░█░██░░░████░███░░███░░██░░░
-███░░█░░█░████░█░█░░█░░█░█░░█░█░░░░█░█░░
-█░█░░█░░█░█░░█░█░█░░█░░█░█░░█░█░░░░█░█░░
-█░░█░░██░░█░░█░█░░█░█░░█░███░░████░█░░█░
-"), // RUAKHBEK
+███████░░░░░░░███████░░░░░░░███████░░░░░"),
+    include_input!(22 10) => ("13220", "RUAKHBEK"),
 }
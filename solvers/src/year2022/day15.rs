@@ -1,5 +1,7 @@
+use itertools::Itertools;
+
 use common::prelude::*;
-use crate::utils::OkIterator;
+use crate::utils::{Interval, IntervalSet, OkIterator};
 
 #[derive(Debug)]
 struct SensorData {
@@ -42,36 +44,15 @@ impl SensorData {
     //   .   B
     //    . .
     //     .
-    fn x_interval(&self, y: i64) -> Option<(i64, i64)> {
+    fn x_interval(&self, y: i64) -> Option<Interval<i64>> {
         // Remaining distance left for the x-axis:
         let xd = self.manhattan() - (self.sensor.1 - y).abs();
-        (xd >= 0).then_some((self.sensor.0 - xd, self.sensor.0 + xd))
+        (xd >= 0).then(|| Interval::inclusive(self.sensor.0 - xd, self.sensor.0 + xd))
     }
 
-    fn x_intervals(datas: &[Self], y: i64, maxi: Option<i64>, intervals: &mut Vec<(i64, i64)>) {
-        intervals.clear();
-        intervals.extend(datas.iter().filter_map(|data| {
-            let x_int = data.x_interval(y)?;
-            match maxi {
-                // No restriction to "0..=maxi".
-                None => Some(x_int),
-                // Outside "0..=maxi".
-                Some(m) if x_int.1 < 0 || x_int.0 > m => None,
-                // Restrain it to "0..=maxi".
-                Some(m) => Some((x_int.0.max(0), x_int.1.min(m))),
-            }
-        }));
-        intervals.sort_unstable();
-        let mut cur_x = i64::MIN;
-        intervals.retain(|x_int| {
-            if x_int.1 <= cur_x {
-                // This interval is contained by the previous one.
-                false
-            } else {
-                cur_x = x_int.1;
-                true
-            }
-        });
+    fn x_intervals(datas: &[Self], y: i64, xs: &mut IntervalSet<i64>) {
+        xs.clear();
+        xs.extend(datas.iter().filter_map(|data| data.x_interval(y)));
     }
 }
 
@@ -83,35 +64,29 @@ pub fn solver(part: Part, input: &str) -> Result<i64> {
         .iter()
         .flat_map(|data| [data.sensor.0, data.sensor.1, data.beacon.0, data.beacon.1])
         .all(|x| x < 1000);
-    let mut intervals = Vec::with_capacity(datas.len());
+    let mut xs = IntervalSet::new();
     match part {
         Part1 => {
             let y = if small { 10 } else { 2_000_000 };
-            SensorData::x_intervals(&datas, y, None, &mut intervals);
-            Ok(intervals
-                .into_iter()
-                .fold((0, i64::MIN), |(total, cur_x), (x0, x1)| {
-                    let interval_length = (x0.max(cur_x) - x1).abs();
-                    (total + interval_length, x1)
-                })
-                .0)
+            SensorData::x_intervals(&datas, y, &mut xs);
+            // Known beacons are in the intervals, but there obviously is a beacon there.
+            let nb_beacons = datas
+                .iter()
+                .filter(|data| data.beacon.1 == y)
+                .map(|data| data.beacon.0)
+                .unique()
+                .count();
+            Ok(xs.total_len() - i64::try_from(nb_beacons)?)
         }
         Part2 => {
             let maxi = if small { 20 } else { 4_000_000 };
+            let bounds = Interval::inclusive(0, maxi);
             (0..=maxi)
                 .rev() // faster than without, but that's just fortunate!
                 .find_map(|y| {
-                    let mut cur_x = i64::MIN;
-                    SensorData::x_intervals(&datas, y, Some(maxi), &mut intervals);
-                    let x = intervals.iter().copied().find_map(|(x0, x1)| {
-                        if cur_x + 1 < x0 && cur_x != i64::MIN {
-                            // `cur_x + 1` is in the hole and we were told it is the only one.
-                            Some(cur_x + 1)
-                        } else {
-                            cur_x = x1;
-                            None
-                        }
-                    })?;
+                    SensorData::x_intervals(&datas, y, &mut xs);
+                    // We were told there is only one hole.
+                    let x = xs.gaps(bounds).next()?.start;
                     #[cfg(debug_assertions)]
                     println!("{:?}", (x, y));
                     // NOTE: The type i64 is required for this multiplication:
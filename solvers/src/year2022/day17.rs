@@ -1,5 +1,5 @@
 use common::prelude::*;
-use crate::utils::OkIterator;
+use crate::utils::{cycle_detect, Cycle, OkIterator};
 
 const CAVE_WIDTH: usize = 7;
 const NB_ROCKS: usize = 5;
@@ -37,8 +37,10 @@ struct Loc(usize, usize);
 
 #[derive(Debug)]
 struct TetrisSolver {
-    period_detector: HashMap<String, (usize, usize)>,
-    jets: std::iter::Cycle<std::vec::IntoIter<JetDirection>>,
+    jets: Vec<JetDirection>,
+    jet_index: usize,
+    nb_rocks: usize,
+    height: usize,
     resting_rocks: HashSet<Loc>,
 }
 
@@ -138,21 +140,13 @@ impl TetrisSolver {
         text
     }
 
-    fn feed_detector(&mut self, step: usize, height: usize) -> Option<(usize, usize)> {
-        let key = self.visualize(Some(100));
-        if let Some((prev_step, prev_height)) = self.period_detector.get(&key) {
-            Some((step - prev_step, height - prev_height))
-        } else {
-            self.period_detector.insert(key, (step, height));
-            None
-        }
-    }
-
-    #[allow(clippy::expect_used)]
-    fn throw_rock(&mut self, rock: Rock, mut loc: Loc) -> usize {
+    fn throw_rock(&mut self) {
+        let rock = Rock::ALL[self.nb_rocks % NB_ROCKS];
+        let mut loc = Loc::new(self.height);
         loop {
             // NOTE: "push" checks for collisions with walls.
-            let new_loc = loc.push(self.jets.next().expect("non-empty cycle"), rock);
+            let new_loc = loc.push(self.jets[self.jet_index], rock);
+            self.jet_index = (self.jet_index + 1) % self.jets.len();
             if !self.is_collision(rock, new_loc) {
                 // No collision with other rocks.
                 loc = new_loc;
@@ -167,35 +161,31 @@ impl TetrisSolver {
         }
         // Come to rest
         self.resting_rocks.extend(rock.locs(loc));
-        loc.height()
+        self.height = self.height.max(loc.height() + rock.height());
+        self.nb_rocks += 1;
     }
 
-    fn solve(mut self, nb_steps: usize) -> usize {
-        let mut height = 0;
-        let mut skipped_height = 0;
-        let mut step = 0;
-        while step != nb_steps {
-            debug_assert_eq!(step % NB_ROCKS, 0);
-            if let Some((period, periodic_height)) = self.feed_detector(step / NB_ROCKS, height) {
-                let steps_in_one_period = period * NB_ROCKS;
-                let nb_skip_periods = (nb_steps - step) / steps_in_one_period;
-                step += nb_skip_periods * steps_in_one_period;
-                skipped_height += nb_skip_periods * periodic_height;
-            }
-            if step == nb_steps {
-                break;
-            }
-            for rock in Rock::ALL {
-                let resting_height = self.throw_rock(rock, Loc::new(height));
-                height = height.max(resting_height + rock.height());
-                step += 1;
-                if step == nb_steps {
-                    break;
+    fn solve(mut self, nb_rocks: usize) -> Result<usize> {
+        let mut heights = vec![0];
+        // Rocks are thrown five at a time so that the key is only built when the rock index lines up.
+        // The top of the tower is assumed to be enough to know what happens next.
+        let rounds = cycle_detect(
+            &mut self,
+            |tetris| {
+                for _ in 0..NB_ROCKS {
+                    tetris.throw_rock();
+                    heights.push(tetris.height);
                 }
-            }
-        }
-        // self.visualize(None);
-        height + skipped_height
+            },
+            |tetris| (tetris.jet_index, tetris.visualize(Some(100))),
+        );
+        let cycle = Cycle {
+            start: rounds.start * NB_ROCKS,
+            period: rounds.period * NB_ROCKS,
+        };
+        cycle
+            .extrapolate(&heights, nb_rocks)
+            .context("Height overflow")
     }
 
     fn is_collision(&self, rock: Rock, loc: Loc) -> bool {
@@ -220,8 +210,10 @@ impl std::str::FromStr for TetrisSolver {
             .ok_collect_vec()?;
         ensure!(!jet_patterns.is_empty(), "No jet");
         Ok(Self {
-            period_detector: HashMap::new(),
-            jets: jet_patterns.into_iter().cycle(),
+            jets: jet_patterns,
+            jet_index: 0,
+            nb_rocks: 0,
+            height: 0,
             resting_rocks: HashSet::new(),
         })
     }
@@ -230,7 +222,7 @@ impl std::str::FromStr for TetrisSolver {
 /// Pyroclastic Flow
 pub fn solver(part: Part, input: &str) -> Result<usize> {
     let nb_steps = part.value(2022, 1_000_000_000_000);
-    Ok(input.parse::<TetrisSolver>()?.solve(nb_steps))
+    input.parse::<TetrisSolver>()?.solve(nb_steps)
 }
 
 test_solver! {
@@ -3,7 +3,7 @@ use std::iter::repeat;
 use itertools::Itertools;
 
 use common::prelude::*;
-use crate::utils::{parse_to_grid, OkIterator};
+use crate::utils::{parse_to_grid, Grid, OkIterator, Point3};
 
 #[derive(Debug, Clone, Copy)]
 enum Direction {
@@ -13,13 +13,43 @@ enum Direction {
     Up,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 enum Tile {
     Open,
     Void,
     Wall,
 }
 
+impl Tile {
+    #[cfg(debug_assertions)]
+    fn to_char(&self) -> char {
+        match self {
+            Self::Open => '.',
+            Self::Void => '+',
+            Self::Wall => '#',
+        }
+    }
+}
+
+/// A face of the cube: where it is in the grid, and how it is oriented in space.
+#[derive(Debug)]
+struct Face {
+    /// Top left location in the grid.
+    origin: (usize, usize),
+    /// Outward normal.
+    normal: Point3<i32>,
+    /// Directions of the face columns and rows in space.
+    right: Point3<i32>,
+    down: Point3<i32>,
+}
+
+/// The grid folded into a cube.
+#[derive(Debug)]
+struct Cube {
+    size: usize,
+    faces: Vec<Face>,
+}
+
 #[derive(Debug)]
 enum Instruction {
     Left,
@@ -28,6 +58,8 @@ enum Instruction {
 }
 
 impl Direction {
+    const ALL: [Self; 4] = [Self::Down, Self::Left, Self::Right, Self::Up];
+
     const fn turn_left(self) -> Self {
         match self {
             Self::Down => Self::Right,
@@ -62,31 +94,15 @@ impl Direction {
     }
 }
 
-#[allow(clippy::match_on_vec_items)] // I made sure the grid is rectangular, it should not panic.
 fn follow_instructions(
     instructions: &[Instruction],
-    grid: &[Vec<Tile>],
+    grid: &Grid<Tile>,
 ) -> Result<(usize, usize, Direction)> {
     #[cfg(debug_assertions)]
-    for row in grid {
-        for tile in row {
-            match tile {
-                Tile::Open => print!("."),
-                Tile::Void => print!("+"),
-                Tile::Wall => print!("#"),
-            }
-        }
-        println!();
-    }
+    print!("{}", grid.display(Tile::to_char));
     let mut d = Direction::Right;
     let (mut r, mut c) = grid
-        .iter()
-        .enumerate()
-        .find_map(|(r, row)| {
-            row.iter()
-                .enumerate()
-                .find_map(|(c, tile)| (tile == &Tile::Open).then_some((r, c)))
-        })
+        .find(|tile| tile == &Tile::Open)
         .context("No opening tile")?;
     for instruction in instructions {
         match instruction {
@@ -96,12 +112,12 @@ fn follow_instructions(
                 let (mut r0, mut c0) = (r, c);
                 for _ in 0..*n {
                     let (mut r1, mut c1) = d.drc(r0, c0);
-                    match grid[r1][c1] {
+                    match grid[(r1, c1)] {
                         Tile::Open => (r0, c0) = (r1, c1),
                         Tile::Wall => break,
                         Tile::Void => {
                             (r1, c1) = find_position_after_void(grid, r1, c1, d)?;
-                            match grid[r1][c1] {
+                            match grid[(r1, c1)] {
                                 Tile::Open => (r0, c0) = (r1, c1),
                                 Tile::Wall => break,
                                 Tile::Void => bail!("Void --> Void"),
@@ -118,47 +134,32 @@ fn follow_instructions(
 }
 
 fn find_position_after_void(
-    grid: &[Vec<Tile>],
+    grid: &Grid<Tile>,
     r: usize,
     c: usize,
     d: Direction,
 ) -> Result<(usize, usize)> {
     let mut positions: Box<dyn Iterator<Item = (usize, usize)>> = match d {
         Direction::Down => Box::new((0..=r).zip(repeat(c))),
-        Direction::Up => Box::new((r..grid.len()).rev().zip(repeat(c))),
+        Direction::Up => Box::new((r..grid.nrows()).rev().zip(repeat(c))),
         Direction::Right => Box::new(repeat(r).zip(0..=c)),
-        Direction::Left => Box::new(repeat(r).zip((c..grid[r].len()).rev())),
+        Direction::Left => Box::new(repeat(r).zip((c..grid.ncols()).rev())),
     };
     positions
-        .find(|(r0, c0)| grid[*r0][*c0] != Tile::Void)
+        .find(|loc| grid[*loc] != Tile::Void)
         .context("Could not find non-void position")
 }
 
-#[allow(clippy::match_on_vec_items)] // I made sure the grid is rectangular, it should not panic.
 fn follow_instructions_v2(
     instructions: &[Instruction],
-    grid: &[Vec<Tile>],
+    grid: &Grid<Tile>,
+    cube: &Cube,
 ) -> Result<(usize, usize, Direction)> {
     #[cfg(debug_assertions)]
-    for row in grid {
-        for tile in row {
-            match tile {
-                Tile::Open => print!("."),
-                Tile::Void => print!("+"),
-                Tile::Wall => print!("#"),
-            }
-        }
-        println!();
-    }
+    print!("{}", grid.display(Tile::to_char));
     let mut d = Direction::Right;
     let (mut r, mut c) = grid
-        .iter()
-        .enumerate()
-        .find_map(|(r, row)| {
-            row.iter()
-                .enumerate()
-                .find_map(|(c, tile)| (tile == &Tile::Open).then_some((r, c)))
-        })
+        .find(|tile| tile == &Tile::Open)
         .context("No opening tile")?;
     for instruction in instructions {
         match instruction {
@@ -167,14 +168,13 @@ fn follow_instructions_v2(
             Instruction::Forward(n) => {
                 let (mut r0, mut c0, mut d0) = (r, c, d);
                 for _ in 0..*n {
-                    let (mut r1, mut c1) = d0.drc(r0, c0);
-                    let mut d1 = d0;
-                    match grid[r1][c1] {
+                    let (r1, c1) = d0.drc(r0, c0);
+                    match grid[(r1, c1)] {
                         Tile::Open => (r0, c0) = (r1, c1),
                         Tile::Wall => break,
                         Tile::Void => {
-                            (r1, c1, d1) = find_position_after_void_v2(r1, c1, d1)?;
-                            match grid[r1][c1] {
+                            let (r1, c1, d1) = cube.wrap(r0, c0, d0)?;
+                            match grid[(r1, c1)] {
                                 Tile::Open => (r0, c0, d0) = (r1, c1, d1),
                                 Tile::Wall => break,
                                 Tile::Void => bail!("Void --> Void"),
@@ -190,39 +190,126 @@ fn follow_instructions_v2(
     Ok((r, c, d))
 }
 
-// Only work for this layout, (and side size = 50)
-//  ---
-// | ##|
-// | # |
-// |## |
-// |#  |
-//  ---
-fn find_position_after_void_v2(
-    r: usize,
-    c: usize,
-    d: Direction,
-) -> Result<(usize, usize, Direction)> {
-    Ok(match (r, c, d) {
-        (100, 1..=50, Direction::Up) => (c + 50, 51, Direction::Right),
-        (0, 51..=100, Direction::Up) => (c + 100, 1, Direction::Right),
-        (0, 101..=150, Direction::Up) => (200, c - 100, Direction::Up),
+impl Face {
+    /// Direction in space of a move on this face.
+    fn towards(&self, d: Direction) -> Point3<i32> {
+        match d {
+            Direction::Down => self.down,
+            Direction::Left => -self.right,
+            Direction::Right => self.right,
+            Direction::Up => -self.down,
+        }
+    }
 
-        (201, 1..=50, Direction::Down) => (1, c + 100, Direction::Down),
-        (151, 51..=100, Direction::Down) => (c + 100, 50, Direction::Left),
-        (51, 101..=150, Direction::Down) => (c - 50, 100, Direction::Left),
+    /// The adjacent face of the net, folded along the edge in direction `d`.
+    fn fold(&self, d: Direction, origin: (usize, usize)) -> Self {
+        let (normal, towards) = (self.towards(d), -self.normal);
+        match d {
+            Direction::Down | Direction::Up => Self {
+                origin,
+                normal,
+                right: self.right,
+                down: if matches!(d, Direction::Down) { towards } else { -towards },
+            },
+            Direction::Left | Direction::Right => Self {
+                origin,
+                normal,
+                right: if matches!(d, Direction::Right) { towards } else { -towards },
+                down: self.down,
+            },
+        }
+    }
+}
 
-        (1..=50, 50, Direction::Left) => (151 - r, 1, Direction::Right),
-        (51..=100, 50, Direction::Left) => (101, r - 50, Direction::Down),
-        (101..=150, 0, Direction::Left) => (151 - r, 51, Direction::Right),
-        (151..=200, 0, Direction::Left) => (1, r - 100, Direction::Down),
+impl Cube {
+    /// Detect the faces of the net and fold them, the grid having a void contour.
+    fn fold(grid: &Grid<Tile>) -> Result<Self> {
+        let nb_tiles = grid.iter().filter(|tile| tile != &&Tile::Void).count();
+        let size = (1..=nb_tiles)
+            .find(|size| 6 * size * size >= nb_tiles)
+            .context("Empty grid")?;
+        ensure!(6 * size * size == nb_tiles, "The net does not have 6 square faces");
+        let is_face = |origin: (usize, usize)| {
+            grid.get(origin).is_some_and(|tile| tile != &Tile::Void)
+        };
+        let start = (1..grid.ncols())
+            .step_by(size)
+            .find(|&c| is_face((1, c)))
+            .context("No face on the top row")?;
+        let mut faces = vec![Face {
+            origin: (1, start),
+            normal: Point3::new(0, 0, 1),
+            right: Point3::new(1, 0, 0),
+            down: Point3::new(0, 1, 0),
+        }];
+        let mut idx = 0;
+        while let Some(face) = faces.get(idx) {
+            let (r, c) = face.origin;
+            let neighbors = [
+                (Direction::Down, Some((r + size, c))),
+                (Direction::Left, c.checked_sub(size).map(|c| (r, c))),
+                (Direction::Right, Some((r, c + size))),
+                (Direction::Up, r.checked_sub(size).map(|r| (r, c))),
+            ];
+            for (d, origin) in neighbors {
+                let Some(origin) = origin else { continue };
+                if is_face(origin) && faces.iter().all(|f| f.origin != origin) {
+                    let new = faces[idx].fold(d, origin);
+                    faces.push(new);
+                }
+            }
+            idx += 1;
+        }
+        ensure!(
+            faces.len() == 6 && faces.iter().map(|face| face.normal).all_unique(),
+            "Not a cube net"
+        );
+        Ok(Self { size, faces })
+    }
 
-        (1..=50, 151, Direction::Right) => (151 - r, 100, Direction::Left),
-        (51..=100, 101, Direction::Right) => (50, r + 50, Direction::Up),
-        (101..=150, 101, Direction::Right) => (151 - r, 150, Direction::Left),
-        (151..=200, 51, Direction::Right) => (150, r - 100, Direction::Up),
+    fn face_at(&self, r: usize, c: usize) -> Result<&Face> {
+        let origin = ((r - 1) / self.size * self.size + 1, (c - 1) / self.size * self.size + 1);
+        self.faces
+            .iter()
+            .find(|face| face.origin == origin)
+            .with_context(|| format_err!("No face at {:?}", (r, c)))
+    }
 
-        _ => bail!("Position: {:?}, {:?}", (r, c), d),
-    })
+    /// Where we arrive when leaving the face at `(r, c)` in direction `d`.
+    fn wrap(&self, r: usize, c: usize, d: Direction) -> Result<(usize, usize, Direction)> {
+        let face = self.face_at(r, c)?;
+        let (ir, ic) = (r - face.origin.0, c - face.origin.1);
+        let next = self
+            .faces
+            .iter()
+            .find(|f| f.normal == face.towards(d))
+            .context("No next face")?;
+        let d1 = Direction::ALL
+            .into_iter()
+            .find(|&d1| next.towards(d1) == -face.normal)
+            .context("No next direction")?;
+        // Position and direction in space along the edge.
+        let (along, tangent) = match d {
+            Direction::Down | Direction::Up => (ic, face.right),
+            Direction::Left | Direction::Right => (ir, face.down),
+        };
+        let next_tangent = match d1 {
+            Direction::Down | Direction::Up => next.right,
+            Direction::Left | Direction::Right => next.down,
+        };
+        let along = if next_tangent == tangent {
+            along
+        } else {
+            self.size - 1 - along
+        };
+        let (nr, nc) = match d1 {
+            Direction::Down => (0, along),
+            Direction::Left => (along, self.size - 1),
+            Direction::Right => (along, 0),
+            Direction::Up => (self.size - 1, along),
+        };
+        Ok((next.origin.0 + nr, next.origin.1 + nc, d1))
+    }
 }
 
 /// Monkey Map
@@ -245,8 +332,9 @@ pub fn solver(part: Part, input: &str) -> Result<usize> {
             row.push(Tile::Void);
         }
     }
-    grid.insert(0, (0..ncols).map(|_| Tile::Void).collect());
-    grid.push((0..ncols).map(|_| Tile::Void).collect());
+    grid.insert(0, vec![Tile::Void; ncols]);
+    grid.push(vec![Tile::Void; ncols]);
+    let grid = Grid::from_rows(grid)?;
     let instructions = line
         .chars()
         .chunk_by(char::is_ascii_digit)
@@ -266,7 +354,7 @@ pub fn solver(part: Part, input: &str) -> Result<usize> {
         .ok_collect_vec()?;
     let (r, c, d) = match part {
         Part1 => follow_instructions(&instructions, &grid)?,
-        Part2 => follow_instructions_v2(&instructions, &grid)?,
+        Part2 => follow_instructions_v2(&instructions, &grid, &Cube::fold(&grid)?)?,
     };
     Ok(1000 * r + 4 * c + d.facing())
 }
@@ -286,6 +374,6 @@ test_solver! {
         ......#.
 
 10R5L5R10L4R5L5
-" => (6032, ), // 5031 but the test fails as my implementation relies on my input layout and size.
+" => (6032, 5031),
     include_input!(22 22) => (30552, 184106),
 }
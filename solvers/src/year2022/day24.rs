@@ -1,7 +1,7 @@
-use std::{collections::BinaryHeap, iter::repeat};
+use std::iter::repeat;
 
 use common::prelude::*;
-use crate::utils::{parse_to_grid, HeuristicItem};
+use crate::utils::{parse_to_grid, Search};
 
 #[derive(Debug, Clone, Copy)]
 enum Direction {
@@ -117,33 +117,19 @@ impl BlizzardGrid {
     }
 
     fn find_path(&self, starting_minute: usize) -> Result<usize> {
-        let mut heap = BinaryHeap::from([HeuristicItem::rev(
-            0, // This first heuristic does not matter.
-            (self.start, starting_minute),
-        )]);
-        let mut been = HashSet::new();
-        Ok(loop {
-            let (loc, mut minutes) = heap.pop().context("Should be impossible")?.item;
-            if loc == self.goal {
-                break minutes;
-            }
-            if !been.insert((loc, minutes)) {
-                continue;
-            }
-            minutes += 1;
-            for opt_dir in Direction::ALL {
-                let loc2 = opt_dir.map_or(Some(loc), |d| d.get(loc.0, loc.1));
-                let Some(loc2) = loc2 else {
-                    continue;
-                };
-                if loc2 == self.start || loc2 == self.goal || self.blizzard_free(loc2, minutes) {
-                    heap.push(HeuristicItem::rev(
-                        minutes + self.dist2goal(loc2),
-                        (loc2, minutes),
-                    ));
-                }
-            }
-        })
+        let successors = |&(loc, minutes): &((usize, usize), usize)| {
+            let minutes = minutes + 1;
+            Direction::ALL.into_iter().filter_map(move |opt_dir| {
+                let loc2 = opt_dir.map_or(Some(loc), |d| d.get(loc.0, loc.1))?;
+                (loc2 == self.start || loc2 == self.goal || self.blizzard_free(loc2, minutes))
+                    .then_some(((loc2, minutes), 1))
+            })
+        };
+        let explored = Search::new(successors)
+            .heuristic(|&(loc, _)| self.dist2goal(loc))
+            .run([(self.start, starting_minute)], |(loc, _)| loc == &self.goal);
+        let minutes = explored.cost().context("Should be impossible")?;
+        Ok(starting_minute + minutes)
     }
 
     fn find_multi_path(&mut self, get_back_times: usize) -> Result<usize> {
@@ -2,10 +2,10 @@ use common::prelude::*;
 use crate::utils::OkIterator;
 
 /// Full of Hot Air
-pub fn solver(part: Part, input: &str) -> Result<String> {
+pub fn solver(part: Part, input: &str) -> Result<Answer> {
     Ok(match part {
-        Part1 => int_to_snafu(input.lines().map(snafu_to_int).ok_sum()?),
-        Part2 => SUCCESS.to_owned(),
+        Part1 => int_to_snafu(input.lines().map(snafu_to_int).ok_sum()?).into(),
+        Part2 => Answer::NoPart2,
     })
 }
 
@@ -45,8 +45,6 @@ fn int_to_snafu(mut n: i64) -> String {
     snafu
 }
 
-const SUCCESS: &str = "It's time for the reindeer to get its star-smoothie.";
-
 test_solver! {
     "\
 1=-0-2
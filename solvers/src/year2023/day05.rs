@@ -1,6 +1,7 @@
 use itertools::Itertools;
 
 use common::prelude::*;
+use crate::utils::{Interval, IntervalSet};
 
 struct RangeMaps(Vec<[u64; 3]>);
 
@@ -13,29 +14,20 @@ impl RangeMaps {
             .map_or(seed, |[dst, src, _]| seed - src + dst)
     }
 
-    fn convert_range(&self, seed_range: (u64, u64)) -> Vec<(u64, u64)> {
-        let mut ranges = vec![seed_range];
-        let mut output = vec![];
-        while let Some((start, end)) = ranges.pop() {
-            if self.0.iter().all(|&[dst, src, len]| {
-                let s = start.max(src);
-                let e = end.min(src + len);
-                if s < e {
-                    output.push((s - src + dst, e - src + dst));
-                    if start < s {
-                        ranges.push((start, s));
-                    }
-                    if e < end {
-                        ranges.push((e, end));
-                    }
-                    return false;
-                }
-                true // No intersection!
-            }) {
-                output.push((start, end));
-            }
+    fn convert_set(&self, seeds: &IntervalSet<u64>) -> IntervalSet<u64> {
+        let mut unmapped = seeds.clone();
+        let mut output = IntervalSet::new();
+        for &[dst, src, len] in &self.0 {
+            let source = IntervalSet::from(Interval::new(src, src + len));
+            output.extend(
+                unmapped
+                    .intersection(&source)
+                    .iter()
+                    .map(|iv| Interval::new(iv.start - src + dst, iv.end - src + dst)),
+            );
+            unmapped = unmapped.difference(&source);
         }
-        output
+        output.union(&unmapped)
     }
 }
 
@@ -89,22 +81,17 @@ pub fn solver(part: Part, input: &str) -> Result<u64> {
             .min(),
         Part2 => {
             ensure!(seeds.len() % 2 == 0, "Odd number of seeds");
-            seeds
+            let seeds = seeds
                 .into_iter()
                 .tuples()
-                .flat_map(|(seed, len)| {
-                    maps.iter().fold(vec![(seed, seed + len)], |acc, (_, map)| {
-                        acc.into_iter()
-                            .flat_map(|seed_range| map.convert_range(seed_range))
-                            .collect()
-                    })
-                })
-                .map(|(start, end)| {
-                    debug_assert!(start < end, "Invalid range");
-                    // start..end are all valid but we are looking for the minimum.
-                    start
-                })
-                .min()
+                .map(|(seed, len)| Interval::new(seed, seed + len))
+                .collect();
+            maps.iter()
+                .fold(seeds, |acc, (_, map)| map.convert_set(&acc))
+                // All are valid but we are looking for the minimum.
+                .iter()
+                .next()
+                .map(|iv| iv.start)
         }
     }
     .context("No seed")
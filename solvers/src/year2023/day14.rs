@@ -1,7 +1,7 @@
 use itertools::Itertools;
 
 use common::prelude::*;
-use crate::utils::parse_to_grid;
+use crate::utils::{cycle_detect, Grid};
 
 #[derive(Debug, Clone, Copy)]
 enum Cell {
@@ -10,34 +10,26 @@ enum Cell {
     RoundedRock,
 }
 
-struct Grid {
-    data: Vec<Vec<Cell>>,
-    nrows: usize,
-    ncols: usize,
-}
+struct Dish(Grid<Cell>);
 
 /// Parabolic Reflector Dish
 pub fn solver(part: Part, input: &str) -> Result<usize> {
-    let mut grid: Grid = input.parse()?;
-    match part {
-        Part1 => grid.roll_north(),
+    let mut dish: Dish = input.parse()?;
+    Ok(match part {
+        Part1 => {
+            dish.roll_north();
+            dish.total_load()
+        }
         Part2 => {
-            const NB_CYCLES: u32 = 1_000_000_000;
-            let mut cache = HashMap::with_capacity(150);
-            for step in 0..NB_CYCLES {
-                grid.roll_cycle();
-                if let Some(prev_step) = cache.insert(grid.id(), step) {
-                    let remaining = NB_CYCLES - 1 - step;
-                    let period = step - prev_step;
-                    for _ in 0..remaining % period {
-                        grid.roll_cycle();
-                    }
-                    break;
-                }
-            }
+            const NB_CYCLES: usize = 1_000_000_000;
+            let mut loads = vec![];
+            let cycle = cycle_detect(&mut dish, Dish::roll_cycle, |dish| {
+                loads.push(dish.total_load());
+                dish.id()
+            });
+            loads[cycle.reduce(NB_CYCLES)]
         }
-    }
-    Ok(grid.total_load())
+    })
 }
 
 impl Cell {
@@ -52,10 +44,10 @@ impl Cell {
     }
 }
 
-impl Grid {
+impl Dish {
     fn total_load(&self) -> usize {
-        self.data
-            .iter()
+        self.0
+            .rows()
             .map(|row| row.iter().filter(|cell| cell.is_round()).count())
             .rev()
             .enumerate()
@@ -68,7 +60,7 @@ impl Grid {
     /// NOTE: `64 * 157 > 100 * 100`
     fn id(&self) -> [u64; 157] {
         let mut bits = [0; 157];
-        for (idx, obj) in self.data.iter().flatten().enumerate() {
+        for (idx, obj) in self.0.iter().enumerate() {
             if obj.is_round() {
                 bits[idx / 64] |= 1 << (idx % 64);
             }
@@ -92,11 +84,11 @@ impl Grid {
         R: Iterator<Item = (usize, usize)>,
     {
         for (r, c) in coords {
-            if self.data[r][c].is_round() {
-                let empties = ray((r, c)).take_while(|&(r0, c0)| self.data[r0][c0].is_empty());
-                if let Some((r1, c1)) = empties.last() {
-                    self.data[r][c] = Cell::Empty;
-                    self.data[r1][c1] = Cell::RoundedRock;
+            if self.0[(r, c)].is_round() {
+                let empties = ray((r, c)).take_while(|&loc| self.0[loc].is_empty());
+                if let Some(loc) = empties.last() {
+                    self.0[(r, c)] = Cell::Empty;
+                    self.0[loc] = Cell::RoundedRock;
                 }
             }
         }
@@ -104,71 +96,63 @@ impl Grid {
 
     fn roll_north(&mut self) {
         self.roll_helper(
-            (1..self.nrows).cartesian_product(0..self.ncols),
+            (1..self.0.nrows()).cartesian_product(0..self.0.ncols()),
             |(r, c)| (0..r).rev().map(move |i| (i, c)),
         );
     }
 
     fn roll_south(&mut self) {
-        let nrows = self.nrows;
+        let nrows = self.0.nrows();
         self.roll_helper(
-            (0..nrows - 1).rev().cartesian_product(0..self.ncols),
+            (0..nrows - 1).rev().cartesian_product(0..self.0.ncols()),
             |(r, c)| (r + 1..nrows).map(move |i| (i, c)),
         );
     }
 
     fn roll_west(&mut self) {
         self.roll_helper(
-            (1..self.ncols)
-                .cartesian_product(0..self.nrows)
+            (1..self.0.ncols())
+                .cartesian_product(0..self.0.nrows())
                 .map(|(c, r)| (r, c)),
             |(r, c)| (0..c).rev().map(move |i| (r, i)),
         );
     }
 
     fn roll_east(&mut self) {
-        let ncols = self.ncols;
+        let ncols = self.0.ncols();
         self.roll_helper(
             (0..ncols - 1)
                 .rev()
-                .cartesian_product(0..self.nrows)
+                .cartesian_product(0..self.0.nrows())
                 .map(|(c, r)| (r, c)),
             |(r, c)| (c + 1..ncols).map(move |i| (r, i)),
         );
     }
 }
 
-impl std::str::FromStr for Grid {
+impl std::str::FromStr for Dish {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        let data = parse_to_grid(s.lines(), |ch| match ch {
+        Grid::parse(s, |ch| match ch {
             '.' => Ok(Cell::Empty),
             'O' => Ok(Cell::RoundedRock),
             '#' => Ok(Cell::CubeShapeRock),
             _ => bail!("Wrong char: {}", ch),
-        })?;
-        let nrows = data.len();
-        let ncols = data[0].len();
-        // TODO: ensure the grid is rectangular.
-        Ok(Self { data, nrows, ncols })
+        })
+        .map(Self)
     }
 }
 
 // Useful to visually check the example grid after cycles 1 2 3.
-impl std::fmt::Display for Grid {
+impl std::fmt::Display for Dish {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in &self.data {
-            for obj in row {
-                match obj {
-                    Cell::Empty => f.write_str(".")?,
-                    Cell::RoundedRock => f.write_str("O")?,
-                    Cell::CubeShapeRock => f.write_str("#")?,
-                }
-            }
-            f.write_str("\n")?;
-        }
-        Ok(())
+        let grid = self.0.display(|cell| match cell {
+            Cell::Empty => '.',
+            Cell::RoundedRock => 'O',
+            Cell::CubeShapeRock => '#',
+        });
+        write!(f, "{grid}")
     }
 }
 
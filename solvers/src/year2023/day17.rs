@@ -1,129 +1,59 @@
-use std::collections::BinaryHeap;
-
 use common::prelude::*;
-use crate::utils::{char10, parse_to_grid, HeuristicItem};
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    N,
-    S,
-    W,
-    E,
-}
+use crate::utils::{char10, DenseMap, Dir4, Grid, Point2, Search};
 
 /// Clumsy Crucible
 #[allow(clippy::expect_used)]
 pub fn solver(part: Part, input: &str) -> Result<u16> {
-    let grid = parse_to_grid(input.lines(), char10::<u16>)?;
-    // TODO: ensure the grid is rectangular
-    let nrows = grid.len();
-    let ncols = grid[0].len();
-    let goal = (nrows - 1, ncols - 1);
-
-    let mut frontier = BinaryHeap::new();
-    let mut cost_so_far = HashMap::with_capacity(nrows * ncols);
+    let grid = Grid::parse(input, char10::<u16>)?;
+    let (nrows, ncols) = grid.shape();
+    let goal = Point2::new(ncols - 1, nrows - 1);
     let (min_nb_moves, max_nb_moves) = part.value((1, 3), (4, 10));
-    for dir in [Direction::E, Direction::S] {
-        let Some(loc) = dir.next_loc_by(min_nb_moves, (0, 0), (nrows, ncols)) else {
-            continue;
-        };
-        let heat_loss: u16 = (1..=min_nb_moves)
+    // Heat loss from `loc` (excluded) to some moves in a direction.
+    let heat_loss = |loc, dir: Dir4, nb_moves| -> u16 {
+        (1..=nb_moves)
             .map(|i| {
-                let (r, c) = dir
-                    .next_loc_by(i, (0, 0), (nrows, ncols))
-                    .expect("(0, 0)..=loc segment is inside the grid");
-                grid[r][c]
+                let p = dir
+                    .step_by(i, loc, ncols, nrows)
+                    .expect("the segment is inside the grid");
+                grid[p]
             })
-            .sum();
-        frontier.push(HeuristicItem::rev(heat_loss, (loc, dir, min_nb_moves)));
-        cost_so_far.insert((loc, dir, min_nb_moves), heat_loss);
-    }
-    while let Some(HeuristicItem {
-        item: (loc, dir, count),
-        ..
-    }) = frontier.pop()
-    {
-        if loc == goal {
-            break;
-        }
-        let heat_loss = cost_so_far[&(loc, dir, count)];
-        for new_dir in [Direction::E, Direction::S, Direction::N, Direction::W] {
+            .sum()
+    };
+    // A state is a location, the last direction and the number of moves in that direction.
+    let successors = |&(loc, dir, count): &(Point2<usize>, Dir4, usize)| {
+        Dir4::ALL.into_iter().filter_map(move |new_dir| {
             // Do not go back!
             if new_dir == dir.opposite() {
-                continue;
+                return None;
             }
-            let (nb_moves, new_count, new_loc) = if new_dir == dir {
+            let (nb_moves, new_count) = if new_dir == dir {
                 if count >= max_nb_moves {
-                    continue;
+                    return None;
                 }
-                (1, count + 1, new_dir.next_loc_by(1, loc, (nrows, ncols)))
+                (1, count + 1)
             } else {
                 if count < min_nb_moves {
-                    continue;
+                    return None;
                 }
-                (
-                    min_nb_moves,
-                    min_nb_moves,
-                    new_dir.next_loc_by(min_nb_moves, loc, (nrows, ncols)),
-                )
+                (min_nb_moves, min_nb_moves)
             };
-            if let Some((r0, c0)) = new_loc {
-                let new_heat_loss = heat_loss
-                    + (1..=nb_moves)
-                        .map(|i| {
-                            let (r, c) = new_dir
-                                .next_loc_by(i, loc, (nrows, ncols))
-                                .expect("loc..=(r0, c0) segment is inside the grid");
-                            grid[r][c]
-                        })
-                        .sum::<u16>();
-                if new_heat_loss
-                    < cost_so_far
-                        .get(&((r0, c0), new_dir, new_count))
-                        .copied()
-                        .unwrap_or(u16::MAX)
-                {
-                    cost_so_far.insert(((r0, c0), new_dir, new_count), new_heat_loss);
-                    frontier.push(HeuristicItem::rev(
-                        new_heat_loss,
-                        ((r0, c0), new_dir, new_count),
-                    ));
-                }
-            }
-        }
-    }
-    cost_so_far
-        .iter()
-        .filter_map(|((loc, ..), heat_loss)| (loc == &goal).then_some(*heat_loss))
-        .min()
+            let new_loc = new_dir.step_by(nb_moves, loc, ncols, nrows)?;
+            let cost = heat_loss(loc, new_dir, nb_moves);
+            Some(((new_loc, new_dir, new_count), cost))
+        })
+    };
+    // No move yet, it can only go straight until it can turn.
+    let starts = [Dir4::E, Dir4::S].map(|dir| (Point2::default(), dir, 0));
+    let index = |&(loc, dir, count): &(Point2<usize>, Dir4, usize)| {
+        ((loc.y * ncols + loc.x) * 4 + dir as usize) * (max_nb_moves + 1) + count
+    };
+    Search::new(successors)
+        .visited(DenseMap::new(nrows * ncols * 4 * (max_nb_moves + 1), index))
+        .run(starts, |&(loc, _, count)| loc == goal && count >= min_nb_moves)
+        .cost()
         .context("Goal not reached!")
 }
 
-impl Direction {
-    const fn opposite(self) -> Self {
-        match self {
-            Self::N => Self::S,
-            Self::S => Self::N,
-            Self::W => Self::E,
-            Self::E => Self::W,
-        }
-    }
-
-    fn next_loc_by(
-        self,
-        amount: usize,
-        (r, c): (usize, usize),
-        (nrows, ncols): (usize, usize),
-    ) -> Option<(usize, usize)> {
-        match self {
-            Self::N => r.checked_sub(amount).map(|i| (i, c)),
-            Self::S => (r + amount < nrows).then_some((r + amount, c)),
-            Self::W => c.checked_sub(amount).map(|i| (r, i)),
-            Self::E => (c + amount < ncols).then_some((r, c + amount)),
-        }
-    }
-}
-
 test_solver! {
     "\
 2413432311323
@@ -1,6 +1,7 @@
 use itertools::{Either, Itertools};
 
 use common::prelude::*;
+use crate::utils::{Cuboid, Interval};
 
 #[derive(Debug, Clone, Copy)]
 enum Operation {
@@ -12,7 +13,7 @@ enum Operation {
 struct Condition {
     xmas_idx: u8, // 0 1 2 or 3
     op: Operation,
-    value: u32,
+    value: u64,
 }
 
 #[derive(Debug)]
@@ -73,23 +74,14 @@ pub fn solver(part: Part, input: &str) -> Result<u64> {
                 ])
             })
             .process_results(|it| {
-                it.filter(|xmas: &[u32; 4]| workflows.run_process(start_idx, *xmas))
-                    .map(|xmas| xmas[0] + xmas[1] + xmas[2] + xmas[3])
-                    .map(u64::from)
+                it.filter(|xmas: &[u64; 4]| workflows.run_process(start_idx, *xmas))
+                    .map(|xmas| xmas.iter().sum::<u64>())
                     .sum()
             })?,
         Part2 => {
-            let valid_ranges = workflows.run_process(start_idx, [(1, 4000); 4]);
+            let valid_ranges = workflows.run_process(start_idx, [Interval::inclusive(1, 4000); 4]);
             // println!("{:?} ranges: {:?}", valid_ranges.len(), valid_ranges);
-            valid_ranges
-                .iter()
-                .map(|part| {
-                    part.iter()
-                        .map(|(start, end)| end - start + 1)
-                        .map(u64::from)
-                        .product::<u64>()
-                })
-                .sum()
+            valid_ranges.iter().map(Cuboid::volume).sum()
         }
     })
 }
@@ -99,10 +91,10 @@ trait WorkflowProcess<T> {
     fn run_process(&self, start: usize, xmas: [T; 4]) -> Self::Output;
 }
 
-impl WorkflowProcess<u32> for IndexedWorkflows {
+impl WorkflowProcess<u64> for IndexedWorkflows {
     type Output = bool;
 
-    fn run_process(&self, start: usize, xmas: [u32; 4]) -> Self::Output {
+    fn run_process(&self, start: usize, xmas: [u64; 4]) -> Self::Output {
         let mut idx = start;
         loop {
             for item in &self[idx] {
@@ -128,10 +120,10 @@ impl WorkflowProcess<u32> for IndexedWorkflows {
     }
 }
 
-impl WorkflowProcess<(u32, u32)> for IndexedWorkflows {
-    type Output = Vec<[(u32, u32); 4]>;
+impl WorkflowProcess<Interval<u64>> for IndexedWorkflows {
+    type Output = Vec<Cuboid<u64, 4>>;
 
-    fn run_process(&self, start: usize, xmas_ranges: [(u32, u32); 4]) -> Self::Output {
+    fn run_process(&self, start: usize, xmas_ranges: [Interval<u64>; 4]) -> Self::Output {
         let mut jobs = vec![(start, xmas_ranges)];
         let mut valid_ranges = vec![];
         while let Some((idx, mut ranges)) = jobs.pop() {
@@ -156,7 +148,7 @@ impl WorkflowProcess<(u32, u32)> for IndexedWorkflows {
                     match item.result {
                         Either::Left(idx) => jobs.push((idx, part)),
                         Either::Right(false) => {}
-                        Either::Right(true) => valid_ranges.push(part),
+                        Either::Right(true) => valid_ranges.push(Cuboid(part)),
                     }
                 }
                 match failure {
@@ -171,7 +163,7 @@ impl WorkflowProcess<(u32, u32)> for IndexedWorkflows {
 
 impl Operation {
     #[inline]
-    const fn eval(self, n: u32, value: u32) -> bool {
+    const fn eval(self, n: u64, value: u64) -> bool {
         match self {
             Self::GreaterThan => n > value,
             Self::LowerThan => n < value,
@@ -179,26 +171,13 @@ impl Operation {
     }
 
     // [Good range?, Bad range?]
-    const fn split_range(self, range: (u32, u32), value: u32) -> [Option<(u32, u32)>; 2] {
+    fn split_range(self, range: Interval<u64>, value: u64) -> [Option<Interval<u64>>; 2] {
         match self {
             Self::GreaterThan => {
-                if range.1 <= value {
-                    [None, Some(range)]
-                } else if value < range.0 {
-                    [Some(range), None]
-                } else {
-                    [Some((value + 1, range.1)), Some((range.0, value))]
-                }
-            }
-            Self::LowerThan => {
-                if value <= range.0 {
-                    [None, Some(range)]
-                } else if range.1 < value {
-                    [Some(range), None]
-                } else {
-                    [Some((range.0, value - 1)), Some((value, range.1))]
-                }
+                let [bad, good] = range.split_at(value + 1);
+                [good, bad]
             }
+            Self::LowerThan => range.split_at(value),
         }
     }
 }
@@ -129,7 +129,7 @@ pub fn solver(part: Part, input: &str) -> Result<u64> {
     }
 }
 
-fn parse(input: &str) -> Result<(Names, Modules<usize>)> {
+fn parse(input: &str) -> Result<(Names<'_>, Modules<usize>)> {
     // Parse modules and destinations
     let mut modules: Modules<&str> = input
         .lines()
@@ -1,7 +1,7 @@
 use std::collections::VecDeque;
 
 use common::prelude::*;
-use crate::utils::{neighbors, parse_to_grid_with_loc};
+use crate::utils::Grid;
 
 #[derive(Debug, Clone)]
 enum Cell {
@@ -12,7 +12,7 @@ enum Cell {
 #[derive(Debug)]
 struct Garden {
     start: (usize, usize),
-    grid: Vec<Vec<Cell>>,
+    grid: Grid<Cell>,
 }
 
 /// Step Counter
@@ -26,22 +26,15 @@ pub fn solver(part: Part, input: &str) -> Result<u64> {
 }
 
 impl Garden {
-    fn shape(&self) -> (usize, usize) {
-        let nrows = self.grid.len();
-        let ncols = self.grid[0].len();
-        (nrows, ncols)
-    }
-
     // Simple BFS
     fn read_distances(&mut self) {
-        let (nrows, ncols) = self.shape();
         let mut queue = VecDeque::from([(0, self.start)]);
-        while let Some((dist, (r, c))) = queue.pop_front() {
-            if let Cell::GardenPlot(rc_dist @ None) = &mut self.grid[r][c] {
-                *rc_dist = Some(dist);
-                for (r0, c0) in neighbors((r, c), nrows, ncols, false) {
-                    if matches!(self.grid[r0][c0], Cell::GardenPlot(None)) {
-                        queue.push_back((dist + 1, (r0, c0)));
+        while let Some((dist, loc)) = queue.pop_front() {
+            if let Cell::GardenPlot(loc_dist @ None) = &mut self.grid[loc] {
+                *loc_dist = Some(dist);
+                for loc0 in self.grid.neighbors(loc, false) {
+                    if matches!(self.grid[loc0], Cell::GardenPlot(None)) {
+                        queue.push_back((dist + 1, loc0));
                     }
                 }
             }
@@ -51,14 +44,13 @@ impl Garden {
     fn exact_steps_no_infinite(&self, steps: u32) -> u64 {
         self.grid
             .iter()
-            .flatten()
             .filter(|cell| matches!(cell, Cell::GardenPlot(Some(dist)) if *dist <= steps && *dist % 2 == steps % 2))
             .count() as u64
     }
 
     #[allow(clippy::cast_possible_truncation)]
     fn exact_steps(&self, steps: u32) -> u64 {
-        let size = self.shape().0;
+        let size = self.grid.nrows();
         // The square grid has 4 corner zones:
         // +-----+
         // |  ^  |
@@ -79,13 +71,11 @@ impl Garden {
         let whole_even = self
             .grid
             .iter()
-            .flatten()
             .filter(|cell| matches!(cell, Cell::GardenPlot(Some(dist)) if *dist % 2 == 0))
             .count() as u64;
         let whole_odd = self
             .grid
             .iter()
-            .flatten()
             .filter(|cell| matches!(cell, Cell::GardenPlot(Some(dist)) if *dist % 2 == 1))
             .count() as u64;
         // The whole center square (3x3 with x) is repeated 1 (center) + 4 * 2k for k in 1..
@@ -137,9 +127,7 @@ impl Garden {
                     .sum::<u64>()
             + self
                 .grid
-                .iter()
                 .enumerate()
-                .flat_map(|(r, col)| col.iter().enumerate().map(move |(c, cell)| ((r, c), cell)))
                 .map(|((r, c), cell)| {
                     if let Cell::GardenPlot(Some(dist)) = cell {
                         if dist % 2 == q % 2 {
@@ -172,7 +160,7 @@ impl std::str::FromStr for Garden {
 
     fn from_str(s: &str) -> Result<Self> {
         let mut start = None;
-        let grid = parse_to_grid_with_loc(s.lines(), |loc, ch| match ch {
+        let grid = Grid::parse_with_loc(s, |loc, ch| match ch {
             '#' => Ok(Cell::Rock),
             '.' => Ok(Cell::GardenPlot(None)),
             'S' => {
@@ -181,7 +169,6 @@ impl std::str::FromStr for Garden {
             }
             _ => bail!("Wrong char: {}", ch),
         })?;
-        // TODO: ensure the grid is rectangular.
         let start = start.context("No start")?;
         Ok(Self { start, grid })
     }
@@ -200,7 +187,7 @@ impl std::fmt::Display for Cell {
 
 impl std::fmt::Display for Garden {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in &self.grid {
+        for row in self.grid.rows() {
             for cell in row {
                 write!(f, "{cell}")?;
             }
@@ -59,7 +59,6 @@ pub fn solver(part: Part, input: &str) -> Result<usize> {
             .context("Wrong direction")?;
         let mut path_length = 1usize;
         loop {
-            #[allow(clippy::match_on_vec_items)]
             let nexts = [Direction::S, Direction::E, Direction::N, Direction::W]
                 .into_iter()
                 .filter(|d| d.opposite() != dir)
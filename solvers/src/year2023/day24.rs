@@ -1,10 +1,7 @@
-use std::fmt::Write;
-use std::process::Stdio;
-
 use itertools::Itertools;
 
 use common::prelude::*;
-use crate::utils::OkIterator;
+use crate::utils::{solve_linear_integral, OkIterator};
 
 #[derive(Debug)]
 struct Hailstone {
@@ -17,7 +14,7 @@ pub fn solver(part: Part, input: &str) -> Result<usize> {
     let hailstones: Vec<Hailstone> = input.lines().map(str::parse).try_collect()?;
     Ok(match part {
         Part1 => {
-            let area = if input == INPUTS[0] {
+            let area = if input == EXAMPLES[0] {
                 (7, 27)
             } else {
                 (200_000_000_000_000, 400_000_000_000_000)
@@ -29,63 +26,55 @@ pub fn solver(part: Part, input: &str) -> Result<usize> {
                 .count()
         }
         Part2 => {
-            let mut z3text = "\
-                (declare-const x0 Real) (declare-const vx0 Real)
-                (declare-const y0 Real) (declare-const vy0 Real)
-                (declare-const z0 Real) (declare-const vz0 Real)"
-                .to_owned();
-            hailstones.into_iter().enumerate().try_for_each(
-                |(
-                    i,
-                    Hailstone {
-                        position: [px, py, pz],
-                        velocity: [vx, vy, vz],
-                    },
-                )| {
-                    z3text.write_fmt(format_args!(
-                        "(declare-const t{i} Real)
-                        (assert (= (+ (to_real {px}) (* t{i} (to_real {vx}))) (+ x0 (* t{i} vx0))))
-                        (assert (= (+ (to_real {py}) (* t{i} (to_real {vy}))) (+ y0 (* t{i} vy0))))
-                        (assert (= (+ (to_real {pz}) (* t{i} (to_real {vz}))) (+ z0 (* t{i} vz0))))"
-                    ))
-                },
-            )?;
-            z3text.push_str("(check-sat) (get-model)");
-            #[cfg(debug_assertions)]
-            z3text.push_str(
-                "(echo \"position:\") (eval x0) (eval y0) (eval z0)
-                (echo \"velocity:\") (eval vx0) (eval vy0) (eval vz0)
-                (echo \"answer:\")",
-            );
-            z3text.push_str("(eval (+ x0 y0 z0))");
-            let mut child = std::process::Command::new("z3")
-                .arg("-in")
-                .stdin(Stdio::piped())
-                .stdout(Stdio::piped())
-                .spawn()?;
-            let mut stdin = child.stdin.take().context("failed to get stdin")?;
-            #[allow(clippy::expect_used)]
-            std::thread::spawn(move || {
-                use std::io::Write;
-                stdin
-                    .write_all(z3text.as_bytes())
-                    .expect("failed to write to stdin");
-            });
-            let output = child.wait_with_output()?;
-            ensure!(output.status.success());
-            let text = String::from_utf8(output.stdout)?;
+            let rock = hailstones
+                .iter()
+                .tuple_combinations()
+                .find_map(|(a, b, c)| Hailstone::rock(a, b, c).ok())
+                .context("No rock position")?;
             #[cfg(debug_assertions)]
-            println!("{text}");
-            let res: f64 = text.lines().last().context("No output")?.parse()?;
-            #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
-            {
-                res.round() as usize
-            }
+            println!("position: {:?}\nvelocity: {:?}", &rock[..3], &rock[3..]);
+            usize::try_from(rock[..3].iter().sum::<i128>())?
         }
     })
 }
 
+fn cross(a: [i128; 3], b: [i128; 3]) -> [i128; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn sub(a: [i128; 3], b: [i128; 3]) -> [i128; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
 impl Hailstone {
+    /// The rock's position and velocity `[px, py, pz, vx, vy, vz]` to hit the three hailstones.
+    ///
+    /// For each hailstone, `(P - p) x (V - v) == 0` and the differences of those equations
+    /// for two hailstones are linear: `P x (v2 - v1) + (p2 - p1) x V == p2 x v2 - p1 x v1`.
+    fn rock(h1: &Self, h2: &Self, h3: &Self) -> Result<[i128; 6]> {
+        let (mut a, mut b) = (vec![], vec![]);
+        for other in [h2, h3] {
+            let [dvx, dvy, dvz] = sub(other.velocity, h1.velocity);
+            let [dpx, dpy, dpz] = sub(other.position, h1.position);
+            a.extend([
+                vec![0, dvz, -dvy, 0, -dpz, dpy],
+                vec![-dvz, 0, dvx, dpz, 0, -dpx],
+                vec![dvy, -dvx, 0, -dpy, dpx, 0],
+            ]);
+            b.extend(sub(
+                cross(other.position, other.velocity),
+                cross(h1.position, h1.velocity),
+            ));
+        }
+        solve_linear_integral(&a, &b)?
+            .try_into()
+            .map_err(|_| format_err!("Not 6 unknowns"))
+    }
+
     #[allow(clippy::cast_precision_loss, clippy::similar_names)]
     fn xy_cross(&self, other: &Self, area: (i128, i128)) -> bool {
         let [px1, py1, _] = self.position;
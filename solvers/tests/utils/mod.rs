@@ -52,19 +52,18 @@ macro_rules! test_collection {
                 #[allow(non_snake_case)] // Usernames are not formatted.
                 #[test]
                 fn [<$username _ $year>]() -> common::Result<()> {
+                    let provider = solvers::InputProvider::default();
                     let year = $year.to_string().parse()?;
                     $(
                         let day = $day.to_string().parse()?;
                         if let Ok((solver, _)) = solvers::aoc(year, day) {
-                            let input = include_str!(concat!(
-                                "../../inputs/other/",
-                                stringify!($username),
-                                "/20",
-                                stringify!($year),
-                                "/",
-                                stringify!($day),
-                                ".txt"
-                            ));
+                            let input = provider
+                                .other_input(stringify!($username), year, day)?
+                                .ok_or_else(|| common::format_err!(
+                                    "No input at {}",
+                                    provider.other_input_path(stringify!($username), year, day).display(),
+                                ))?;
+                            let input = input.as_str();
                             test_collection!(@test $username year day solver input Part1 $p1);
                             $(
                                 test_collection!(@test $username year day solver input Part2 $p2);
@@ -82,15 +81,10 @@ macro_rules! test_collection {
                 //         let year = $year.to_string().parse()?;
                 //         let day = $day.to_string().parse()?;
                 //         if let Ok((solver, _)) = solvers::aoc(year, day) {
-                //             let input = include_str!(concat!(
-                //                 "../../inputs/other/",
-                //                 stringify!($username),
-                //                 "/20",
-                //                 stringify!($year),
-                //                 "/",
-                //                 stringify!($day),
-                //                 ".txt"
-                //             ));
+                //             let input = solvers::InputProvider::default()
+                //                 .other_input(stringify!($username), year, day)?
+                //                 .ok_or_else(|| common::format_err!("No input"))?;
+                //             let input = input.as_str();
 
                 //             test_collection!(@test $username year day solver input Part1 $p1);
                 //             $(
@@ -149,11 +143,12 @@ impl From<&'static str> for Literal {
     }
 }
 
-impl PartialEq<Literal> for String {
+impl PartialEq<Literal> for common::Answer {
     fn eq(&self, other: &Literal) -> bool {
-        match other {
-            Literal::Int(n) => self == &n.to_string(),
-            Literal::Str(s) => self.as_str() == *s,
+        match (self, other) {
+            (Self::Int(n), Literal::Int(m)) => u128::try_from(*n).is_ok_and(|n| n == *m),
+            (_, Literal::Int(m)) => self.to_string() == m.to_string(),
+            (_, Literal::Str(s)) => self == s,
         }
     }
 }
@@ -0,0 +1,180 @@
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Minimal time between two requests to the website.
+const MIN_INTERVAL: Duration = Duration::from_secs(2);
+/// File remembering when the last request was made, across runs.
+const LAST_REQUEST_FILE: &str = "last-request";
+
+/// How long a downloaded page stays valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Freshness {
+    /// Inputs never change once published.
+    Forever,
+    /// Descriptions change when part 2 gets unlocked, which a correct submission tells us.
+    /// Until then, they also expire after the duration in case part 1 got solved in the browser.
+    UntilPart2(Duration),
+    /// Calendars change over time.
+    For(Duration),
+}
+
+/// Pages downloaded from the website, stored on disk and keyed by account and URL.
+///
+/// It also rate limits requests to the website, whatever the account.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+    /// Inputs and descriptions differ for each account.
+    account_dir: PathBuf,
+    min_interval: Duration,
+}
+
+impl Cache {
+    /// The cache of the account with the given session cookie.
+    pub fn new<P: Into<PathBuf>>(dir: P, session: &str) -> Self {
+        let dir = dir.into();
+        Self {
+            account_dir: dir.join(format!("{:016x}", fnv1a(session))),
+            dir,
+            min_interval: MIN_INTERVAL,
+        }
+    }
+
+    /// "https://adventofcode.com/2023/day/1/input" is stored in "2023-day-1-input".
+    fn path(&self, url: &str) -> PathBuf {
+        let path = url.split_once("://").map_or(url, |(_, rest)| rest);
+        let path = path.split_once('/').map_or("", |(_, path)| path);
+        let name: String = path
+            .chars()
+            .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '-' })
+            .collect();
+        self.account_dir
+            .join(if name.is_empty() { "index" } else { &name })
+    }
+
+    /// The cached page, if it's still fresh.
+    pub fn get(&self, url: &str, freshness: Freshness) -> io::Result<Option<String>> {
+        let path = self.path(url);
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let younger_than = |ttl: Duration| -> io::Result<bool> {
+            Ok(fs::metadata(&path)?
+                .modified()?
+                .elapsed()
+                .is_ok_and(|age| age < ttl))
+        };
+        let fresh = match freshness {
+            Freshness::Forever => true,
+            Freshness::UntilPart2(ttl) => text.contains("id=\"part2\"") || younger_than(ttl)?,
+            Freshness::For(ttl) => younger_than(ttl)?,
+        };
+        Ok(fresh.then_some(text))
+    }
+
+    pub fn insert(&self, url: &str, text: &str) -> io::Result<()> {
+        fs::create_dir_all(&self.account_dir)?;
+        fs::write(self.path(url), text)
+    }
+
+    /// Forget a page, when we know it changed.
+    pub fn remove(&self, url: &str) -> io::Result<()> {
+        match fs::remove_file(self.path(url)) {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    /// Wait until the website can be requested again, and remember this request.
+    pub fn throttle(&self) -> io::Result<()> {
+        let path = self.dir.join(LAST_REQUEST_FILE);
+        let last = match fs::read_to_string(&path) {
+            Ok(text) => text.trim().parse().ok(),
+            Err(err) if err.kind() == ErrorKind::NotFound => None,
+            Err(err) => return Err(err),
+        };
+        let now = SystemTime::now();
+        if let Some(last) = last.map(|millis| UNIX_EPOCH + Duration::from_millis(millis)) {
+            // The last request can be in the future if the clock changed.
+            let elapsed = now.duration_since(last).unwrap_or_default();
+            thread::sleep(self.min_interval.saturating_sub(elapsed));
+        }
+        // Rounded up to never wait too little.
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis()
+            + 1;
+        fs::create_dir_all(&self.dir)?;
+        fs::write(path, millis.to_string())
+    }
+}
+
+/// A hash that does not change across runs, to not write session cookies on disk.
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    #[test]
+    fn cache() -> io::Result<()> {
+        let dir = std::env::temp_dir().join(format!("aoc-web-cache-{}", std::process::id()));
+        let cache = Cache {
+            min_interval: Duration::from_millis(200),
+            ..Cache::new(&dir, "session=abc")
+        };
+        let other = Cache::new(&dir, "session=def");
+        let input = "https://adventofcode.com/2023/day/1/input";
+        let desc = "https://adventofcode.com/2023/day/1";
+        let calendar = "https://adventofcode.com/2023";
+        assert_eq!(
+            cache.path(input),
+            cache.account_dir.join("2023-day-1-input")
+        );
+        assert_ne!(cache.account_dir, other.account_dir);
+        assert_eq!(cache.get(input, Freshness::Forever)?, None);
+        cache.insert(input, "1abc2")?;
+        cache.insert(desc, "<article class=\"day-desc\">")?;
+        cache.insert(calendar, "calendar")?;
+        assert_eq!(
+            cache.get(input, Freshness::Forever)?.as_deref(),
+            Some("1abc2")
+        );
+        assert_eq!(other.get(input, Freshness::Forever)?, None);
+        let minute = Freshness::For(Duration::from_secs(60));
+        let until_part2 = Freshness::UntilPart2(Duration::from_secs(60));
+        assert!(cache.get(desc, until_part2)?.is_some());
+        assert_eq!(
+            cache.get(desc, Freshness::UntilPart2(Duration::ZERO))?,
+            None
+        );
+        cache.insert(desc, "<h2 id=\"part2\">")?;
+        assert!(cache
+            .get(desc, Freshness::UntilPart2(Duration::ZERO))?
+            .is_some());
+        assert!(cache.get(calendar, minute)?.is_some());
+        assert!(cache
+            .get(calendar, Freshness::For(Duration::ZERO))?
+            .is_none());
+        cache.remove(input)?;
+        cache.remove(input)?;
+        assert_eq!(cache.get(input, Freshness::Forever)?, None);
+        let start = Instant::now();
+        cache.throttle()?;
+        cache.throttle()?;
+        assert!(start.elapsed() >= cache.min_interval);
+        fs::remove_dir_all(dir)
+    }
+}
@@ -0,0 +1,217 @@
+use crate::html::{self, Token};
+use crate::WEBSITE;
+
+/// How to save a puzzle description.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// Markdown
+    Md,
+    /// Plain text
+    Txt,
+    /// The whole webpage
+    Html,
+}
+
+/// Convert a puzzle webpage: the articles describing the puzzle parts are kept.
+#[must_use]
+pub fn convert(page: &str, format: Format) -> String {
+    if format == Format::Html {
+        return page.to_owned();
+    }
+    let mut writer = Writer {
+        markdown: format == Format::Md,
+        ..Writer::default()
+    };
+    for (attrs, article) in html::articles(page) {
+        if html::attribute(attrs, "class").is_some_and(|class| class.contains("day-desc")) {
+            html::tokens(article).for_each(|token| writer.push(token));
+            writer.end_block();
+        }
+    }
+    writer.finish()
+}
+
+#[derive(Debug, Default)]
+struct Writer {
+    markdown: bool,
+    out: String,
+    /// Preformatted text is written at once when it ends.
+    pre: Option<String>,
+    /// Inside inline code, and if it's emphasized.
+    code: Option<bool>,
+    list_depth: usize,
+    links: Vec<Option<String>>,
+}
+
+impl Writer {
+    fn push(&mut self, token: Token) {
+        if let Some(pre) = &mut self.pre {
+            match token {
+                Token::Text(text) => pre.push_str(&html::unescape(text)),
+                Token::Close("pre") => self.end_pre(),
+                _ => {}
+            }
+            return;
+        }
+        match token {
+            Token::Open("h2", _) => {
+                self.end_block();
+                self.markup("## ");
+            }
+            Token::Open("p", _) | Token::Close("h2" | "p") => self.end_block(),
+            Token::Open("pre", _) => {
+                self.end_block();
+                self.pre = Some(String::new());
+            }
+            Token::Open("code", _) => {
+                self.markup("`");
+                self.code = Some(false);
+            }
+            Token::Close("code") => {
+                self.markup("`");
+                if self.code.take() == Some(true) {
+                    self.markup("*");
+                }
+            }
+            // Markdown code can not have emphasis, the whole code is emphasized instead.
+            Token::Open("em", _)
+                if self.code.is_some() && self.markdown && self.out.ends_with('`') =>
+            {
+                self.out.pop();
+                self.out.push_str("*`");
+                self.code = Some(true);
+            }
+            Token::Open("em", _) | Token::Close("em") if self.code.is_some() => {}
+            Token::Open("em", _) | Token::Close("em") => self.markup("*"),
+            Token::Open("a", attrs) => {
+                let href = html::attribute(attrs, "href").map(|href| {
+                    if href.starts_with('/') {
+                        format!("{WEBSITE}{href}")
+                    } else {
+                        html::unescape(href)
+                    }
+                });
+                if href.is_some() {
+                    self.markup("[");
+                }
+                self.links.push(href);
+            }
+            Token::Close("a") => {
+                if let Some(Some(href)) = self.links.pop() {
+                    self.markup(&format!("]({href})"));
+                }
+            }
+            Token::Open("ul" | "ol", _) => {
+                if self.list_depth == 0 {
+                    self.end_block();
+                }
+                self.list_depth += 1;
+            }
+            Token::Close("ul" | "ol") => {
+                self.list_depth = self.list_depth.saturating_sub(1);
+                if self.list_depth == 0 {
+                    self.end_block();
+                }
+            }
+            Token::Open("li", _) => {
+                self.end_line();
+                let indent = "  ".repeat(self.list_depth.saturating_sub(1));
+                self.out.push_str(&indent);
+                self.out.push_str("- ");
+            }
+            Token::Text(text) => self.text(text),
+            _ => {}
+        }
+    }
+
+    /// Only in Markdown.
+    fn markup(&mut self, markup: &str) {
+        if self.markdown {
+            self.out.push_str(markup);
+        }
+    }
+
+    /// Text with collapsed whitespace, and none at the start of a line.
+    fn text(&mut self, text: &str) {
+        let text = html::unescape(text);
+        let mut words = text.split_whitespace();
+        if text.starts_with(char::is_whitespace) && !self.at_line_start() {
+            self.out.push(' ');
+        }
+        if let Some(word) = words.next() {
+            self.out.push_str(word);
+        }
+        for word in words {
+            self.out.push(' ');
+            self.out.push_str(word);
+        }
+        if text.ends_with(char::is_whitespace) && !self.at_line_start() {
+            self.out.push(' ');
+        }
+    }
+
+    fn end_pre(&mut self) {
+        let pre = self.pre.take().unwrap_or_default();
+        let pre = pre.trim_end_matches('\n');
+        if self.markdown {
+            self.out.push_str("```\n");
+            self.out.push_str(pre);
+            self.out.push_str("\n```");
+        } else {
+            let lines: Vec<_> = pre.lines().map(|line| format!("    {line}")).collect();
+            self.out.push_str(&lines.join("\n"));
+        }
+        self.end_block();
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.out.is_empty() || self.out.ends_with('\n') || self.out.ends_with("- ")
+    }
+
+    fn end_line(&mut self) {
+        self.trim_end();
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+    }
+
+    fn end_block(&mut self) {
+        self.end_line();
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    fn trim_end(&mut self) {
+        let len = self.out.trim_end_matches(' ').len();
+        self.out.truncate(len);
+    }
+
+    fn finish(mut self) -> String {
+        let len = self.out.trim_end().len();
+        self.out.truncate(len);
+        if !self.out.is_empty() {
+            self.out.push('\n');
+        }
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_description() {
+        let page = include_str!("../fixtures/description/day.html");
+        assert_eq!(
+            convert(page, Format::Md),
+            include_str!("../fixtures/description/day.md")
+        );
+        assert_eq!(
+            convert(page, Format::Txt),
+            include_str!("../fixtures/description/day.txt")
+        );
+        assert_eq!(convert(page, Format::Html), page);
+    }
+}
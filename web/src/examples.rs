@@ -0,0 +1,205 @@
+use std::fmt::Write;
+
+use common::{Day, Year};
+
+use crate::html::{self, Token};
+
+/// A code block of a puzzle description, and the answers it seems to lead to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub answers: [Option<String>; 2],
+}
+
+/// Extract the code blocks of the puzzle description, without duplicates.
+///
+/// The answer of a part is guessed to be the last emphasized code of its description,
+/// for the last code block before it, ignoring the ones with emphasis which are usually illustrations.
+#[must_use]
+pub fn extract(page: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = vec![];
+    let descriptions = html::articles(page).into_iter().filter(|(attrs, _)| {
+        html::attribute(attrs, "class").is_some_and(|c| c.contains("day-desc"))
+    });
+    // The second part usually refers to the examples of the first one without repeating them.
+    let mut last_example = None;
+    for (part_idx, (_, article)) in descriptions.take(2).enumerate() {
+        let mut pre: Option<String> = None;
+        let mut illustration = false;
+        let (mut in_code, mut in_em) = (false, false);
+        let mut emphasized = String::new();
+        let mut answer = None;
+        for token in html::tokens(article) {
+            match token {
+                Token::Open("pre", _) => {
+                    pre = Some(String::new());
+                    illustration = false;
+                }
+                Token::Close("pre") => {
+                    let input = pre.take().unwrap_or_default();
+                    let input = format!("{}\n", input.trim_end_matches('\n'));
+                    let idx = examples
+                        .iter()
+                        .position(|example| example.input == input)
+                        .unwrap_or_else(|| {
+                            examples.push(Example {
+                                input,
+                                ..Example::default()
+                            });
+                            examples.len() - 1
+                        });
+                    if !illustration {
+                        last_example = Some(idx);
+                    }
+                }
+                Token::Text(text) => {
+                    if let Some(pre) = &mut pre {
+                        pre.push_str(&html::unescape(text));
+                    } else if in_code && in_em {
+                        emphasized.push_str(&html::unescape(text));
+                    }
+                }
+                Token::Open("em", _) if pre.is_some() => illustration = true,
+                _ if pre.is_some() => {}
+                Token::Open("code", _) => in_code = true,
+                Token::Open("em", _) => in_em = true,
+                Token::Close(name @ ("code" | "em")) => {
+                    if name == "code" {
+                        in_code = false;
+                    } else {
+                        in_em = false;
+                    }
+                    let text = std::mem::take(&mut emphasized);
+                    if !text.trim().is_empty() {
+                        answer = last_example.map(|idx| (idx, text.trim().to_owned()));
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Some((idx, answer)) = answer {
+            examples[idx].answers[part_idx] = Some(answer);
+        }
+    }
+    examples
+}
+
+fn is_number(answer: &str) -> bool {
+    answer.parse::<i128>().is_ok() && !answer.starts_with('+')
+}
+
+/// A Rust literal for an answer: a number, or a string if `text` is set.
+fn literal(answer: &str, text: bool) -> String {
+    if text {
+        format!("{answer:?}")
+    } else {
+        answer.to_owned()
+    }
+}
+
+/// Answers as expected by `test_solver!`, `()` for none.
+fn answers_expr(answers: [Option<&str>; 2], text: bool) -> String {
+    match answers.map(|answer| answer.map(|answer| literal(answer, text))) {
+        [Some(p1), Some(p2)] => format!("({p1}, {p2})"),
+        [Some(p1), None] => format!("({p1}, )"),
+        [None, Some(p2)] => format!("((), {p2})"),
+        [None, None] => "()".to_owned(),
+    }
+}
+
+/// A `test_solver!` invocation for the examples that have answers.
+///
+/// All answers share a type: they are all quoted as soon as one of them is not a number.
+#[must_use]
+pub fn snippet(
+    examples: &[Example],
+    year: Year,
+    day: Day,
+    big_answers: [Option<&str>; 2],
+) -> String {
+    let text = examples
+        .iter()
+        .flat_map(|example| example.answers.iter().flatten().map(String::as_str))
+        .chain(big_answers.into_iter().flatten())
+        .any(|answer| !is_number(answer));
+    let mut res = "test_solver! {\n".to_owned();
+    for example in examples {
+        if example.answers == [None, None] {
+            continue;
+        }
+        let answers = answers_expr([0, 1].map(|idx| example.answers[idx].as_deref()), text);
+        let input = example.input.replace('\\', "\\\\").replace('"', "\\\"");
+        let _ = writeln!(res, "    \"\\\n{input}\" => {answers},");
+    }
+    let (year, day) = (i32::from(year) % 100, u8::from(day));
+    let big_answers = answers_expr(big_answers, text);
+    let _ = writeln!(
+        res,
+        "    include_input!({year:0>2} {day:0>2}) => {big_answers},"
+    );
+    res.push_str("}\n");
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{Day7, Year2023};
+
+    use super::*;
+
+    #[test]
+    fn extract_examples() {
+        let examples = extract(include_str!("../fixtures/examples/day.html"));
+        let inputs: Vec<_> = examples.iter().map(|ex| ex.input.as_str()).collect();
+        assert_eq!(inputs, ["1 2\n3 \"4\"\n", "....\n.##.\n", "10\n20\n"]);
+        let answers: Vec<_> = examples.iter().map(|ex| ex.answers.clone()).collect();
+        assert_eq!(
+            answers,
+            [
+                [Some("10".to_owned()), None],
+                [None, None],
+                [None, Some("ABC".to_owned())],
+            ]
+        );
+        assert_eq!(
+            snippet(&examples, Year2023, Day7, [Some("1234"), None]),
+            r#"test_solver! {
+    "\
+1 2
+3 \"4\"
+" => ("10", ),
+    "\
+10
+20
+" => ((), "ABC"),
+    include_input!(23 07) => ("1234", ),
+}
+"#
+        );
+        assert_eq!(
+            snippet(&examples[..1], Year2023, Day7, [Some("1234"), Some("-5")]),
+            r#"test_solver! {
+    "\
+1 2
+3 \"4\"
+" => (10, ),
+    include_input!(23 07) => (1234, -5),
+}
+"#
+        );
+        assert_eq!(answers_expr([None, Some("5")], false), "((), 5)");
+        assert_eq!(answers_expr([None, None], true), "()");
+    }
+
+    #[test]
+    fn part2_refers_to_part1_example() {
+        let examples = extract(include_str!("../fixtures/examples/example_above.html"));
+        let inputs: Vec<_> = examples.iter().map(|ex| ex.input.as_str()).collect();
+        assert_eq!(inputs, ["1 2\n3 4\n", "#.\n<#\n"]);
+        assert_eq!(
+            examples[0].answers,
+            [Some("10".to_owned()), Some("24".to_owned())]
+        );
+        assert_eq!(examples[1].answers, [None, None]);
+    }
+}
@@ -0,0 +1,102 @@
+//! Just enough HTML handling for the pages of adventofcode.com, which are simple and well-formed.
+
+/// The attributes and inner HTML of all `<article>` elements.
+pub fn articles(html: &str) -> Vec<(&str, &str)> {
+    let mut res = vec![];
+    let mut rest = html;
+    while let Some(start) = rest.find("<article") {
+        rest = &rest[start + "<article".len()..];
+        let Some(open_end) = rest.find('>') else {
+            break;
+        };
+        let Some(close) = rest.find("</article>") else {
+            break;
+        };
+        res.push((&rest[..open_end], &rest[open_end + 1..close]));
+        rest = &rest[close..];
+    }
+    res
+}
+
+/// A piece of HTML.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token<'a> {
+    /// Opening tag: name and attributes.
+    Open(&'a str, &'a str),
+    Close(&'a str),
+    Text(&'a str),
+}
+
+/// Split HTML into tags and texts, comments are skipped.
+pub fn tokens(html: &str) -> impl Iterator<Item = Token<'_>> {
+    let mut rest = html;
+    std::iter::from_fn(move || loop {
+        if rest.is_empty() {
+            return None;
+        }
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.split_once("-->").map_or("", |(_, after)| after);
+            continue;
+        }
+        if let Some(tag) = rest.strip_prefix('<') {
+            let (tag, after) = tag.split_once('>').unwrap_or((tag, ""));
+            rest = after;
+            let tag = tag.trim_end_matches('/');
+            return Some(match tag.strip_prefix('/') {
+                Some(name) => Token::Close(name.trim()),
+                None => {
+                    let (name, attrs) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+                    Token::Open(name, attrs.trim())
+                }
+            });
+        }
+        let end = rest.find('<').unwrap_or(rest.len());
+        let text;
+        (text, rest) = rest.split_at(end);
+        return Some(Token::Text(text));
+    })
+}
+
+/// The value of an attribute, such as `href` in `href="/2023/about"`.
+pub fn attribute<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attrs;
+    while let Some(idx) = rest.find(name) {
+        let before = &rest[..idx];
+        rest = &rest[idx + name.len()..];
+        if before.is_empty() || before.ends_with(char::is_whitespace) {
+            if let Some(value) = rest.strip_prefix("=\"") {
+                return value.split_once('"').map(|(value, _)| value);
+            }
+        }
+    }
+    None
+}
+
+/// Decode the few HTML entities the website uses.
+pub fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// The text of some HTML, without tags and with collapsed whitespace.
+pub fn text(html: &str) -> String {
+    let mut res = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => res.push(ch),
+            _ => {}
+        }
+    }
+    unescape(&res)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
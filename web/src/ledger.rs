@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use common::{bail, ensure, line_file, Day, Error, Part, Result, Year};
+
+use crate::{Hint, SubmitVerdict};
+
+/// What the website said about a submitted answer, when it judged it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl Outcome {
+    /// Only verdicts about the answer itself are worth remembering.
+    #[must_use]
+    pub const fn from_verdict(verdict: &SubmitVerdict) -> Option<Self> {
+        match verdict {
+            SubmitVerdict::Correct => Some(Self::Correct),
+            SubmitVerdict::Wrong { hint: None, .. } => Some(Self::Wrong),
+            SubmitVerdict::Wrong {
+                hint: Some(Hint::TooHigh),
+                ..
+            } => Some(Self::TooHigh),
+            SubmitVerdict::Wrong {
+                hint: Some(Hint::TooLow),
+                ..
+            } => Some(Self::TooLow),
+            _ => None,
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Correct => "correct",
+            Self::Wrong => "wrong",
+            Self::TooHigh => "too-high",
+            Self::TooLow => "too-low",
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "correct" => Self::Correct,
+            "wrong" => Self::Wrong,
+            "too-high" => Self::TooHigh,
+            "too-low" => Self::TooLow,
+            v => bail!("Unknown outcome: {}", v),
+        })
+    }
+}
+
+/// History of the answers I submitted, to not submit one that is known to be wrong.
+///
+/// It's stored as a text file with one attempt per line, in submission order:
+/// ```text
+/// # year day part outcome answer
+/// 2023 01 1 too-low 12345
+/// 2023 01 1 correct 54990
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Ledger(BTreeMap<(Year, Day, Part), Vec<(Outcome, String)>>);
+
+impl Ledger {
+    /// Read the ledger file, it's empty if the file does not exist.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        line_file::load(path, "ledger")
+    }
+
+    /// Write the ledger file, comments are not preserved.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        line_file::save(self, path)
+    }
+
+    /// Previous attempts for a puzzle, in submission order.
+    #[must_use]
+    pub fn attempts(&self, year: Year, day: Day, part: Part) -> &[(Outcome, String)] {
+        self.0.get(&(year, day, part)).map_or(&[], Vec::as_slice)
+    }
+
+    /// The accepted answer of a puzzle, if any.
+    #[must_use]
+    pub fn correct(&self, year: Year, day: Day, part: Part) -> Option<&str> {
+        self.attempts(year, day, part)
+            .iter()
+            .find(|(outcome, _)| outcome == &Outcome::Correct)
+            .map(|(_, answer)| answer.as_str())
+    }
+
+    /// Why the answer should not be submitted, if previous attempts tell it can not be right.
+    #[must_use]
+    pub fn refusal(&self, year: Year, day: Day, part: Part, answer: &str) -> Option<String> {
+        if let Some(correct) = self.correct(year, day, part) {
+            return Some(if correct == answer {
+                "this answer was already accepted".to_owned()
+            } else {
+                format!("the answer {correct} was already accepted")
+            });
+        }
+        let number = answer.parse::<i128>().ok();
+        self.attempts(year, day, part)
+            .iter()
+            .find_map(|(outcome, previous)| {
+                if previous == answer {
+                    return Some("this answer was already rejected".to_owned());
+                }
+                let (number, previous) = (number?, previous.parse::<i128>().ok()?);
+                match outcome {
+                    Outcome::TooHigh if number >= previous => {
+                        Some(format!("{previous} was already too high"))
+                    }
+                    Outcome::TooLow if number <= previous => {
+                        Some(format!("{previous} was already too low"))
+                    }
+                    _ => None,
+                }
+            })
+    }
+
+    /// Record an attempt.
+    pub fn record(
+        &mut self,
+        year: Year,
+        day: Day,
+        part: Part,
+        outcome: Outcome,
+        answer: String,
+    ) -> Result<()> {
+        ensure!(
+            !answer.is_empty() && !answer.contains('\n'),
+            "Invalid answer: {:?}",
+            answer
+        );
+        self.0
+            .entry((year, day, part))
+            .or_default()
+            .push((outcome, answer));
+        Ok(())
+    }
+}
+
+impl FromStr for Ledger {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut ledger = Self::default();
+        for mut record in line_file::records(s, 5) {
+            let year = record.field()?.parse()?;
+            let day = record.field()?.parse()?;
+            let part = record.field()?.parse()?;
+            let outcome = record.field()?.parse()?;
+            let answer = record.field()?.to_owned();
+            ledger.record(year, day, part, outcome, answer)?;
+        }
+        Ok(ledger)
+    }
+}
+
+impl fmt::Display for Ledger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# year day part outcome answer")?;
+        for ((year, day, part), attempts) in &self.0 {
+            for (outcome, answer) in attempts {
+                writeln!(
+                    f,
+                    "{} {:0>2} {} {} {}",
+                    i32::from(*year),
+                    u8::from(*day),
+                    part.value(1, 2),
+                    outcome.name(),
+                    answer,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{Day1, Day2, Part1, Part2, Year2023};
+
+    use super::*;
+
+    #[test]
+    fn refusals() -> Result<()> {
+        let text = "\
+# year day part outcome answer
+2023 01 1 too-low 100
+2023 01 1 too-high 200
+2023 01 1 wrong abc
+2023 01 2 wrong 150
+2023 01 2 correct 160
+";
+        let ledger: Ledger = text.parse()?;
+        assert_eq!(ledger.to_string(), text);
+        let refusal = |part, answer| ledger.refusal(Year2023, Day1, part, answer);
+        assert!(refusal(Part1, "150").is_none());
+        assert!(refusal(Part1, "100").is_some());
+        assert!(refusal(Part1, "99").is_some());
+        assert!(refusal(Part1, "250").is_some());
+        assert!(refusal(Part1, "abc").is_some());
+        assert!(refusal(Part1, "abd").is_none());
+        assert!(refusal(Part2, "160").is_some());
+        assert!(refusal(Part2, "170").is_some());
+        assert!(ledger.refusal(Year2023, Day2, Part1, "abc").is_none());
+        assert_eq!(ledger.correct(Year2023, Day1, Part2), Some("160"));
+        assert!("2023 01 1 maybe 5".parse::<Ledger>().is_err());
+        Ok(())
+    }
+}
@@ -2,14 +2,39 @@ use std::env;
 use std::fs;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 use std::time::Duration;
 
 use clap::{Parser, Subcommand};
 
+use common::answers::{Answers, MINE};
 use common::{Day, Part, Year};
 
+mod cache;
+mod description;
+mod examples;
+mod html;
+mod ledger;
+mod submit;
+
+pub use cache::{Cache, Freshness};
+pub use description::Format;
+pub use examples::Example;
+pub use ledger::{Ledger, Outcome};
+pub use submit::{Hint, SubmitVerdict};
+
 const WEBSITE: &str = "https://adventofcode.com";
 
+/// Environment variable that can hold the directory of the puzzle inputs (shared with the solvers).
+const INPUTS_DIR_VAR: &str = "AOC_INPUTS";
+/// Files of the inputs directory for my submitted and accepted answers.
+const LEDGER_FILE: &str = "submissions.txt";
+const ANSWERS_FILE: &str = "answers.txt";
+/// How long the calendar page is cached.
+const CALENDAR_TTL: Duration = Duration::from_secs(15 * 60);
+/// How long a description without part 2 is cached, in case part 1 got solved in the browser.
+const PART1_TTL: Duration = Duration::from_secs(60 * 60);
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug, thiserror::Error)]
@@ -29,11 +54,23 @@ pub enum Error {
     #[error("More than 10mb of text?! {0}")]
     WebsiteString(io::Error),
 
+    #[error("Failed to read from the disk: {0}")]
+    ReadFromDisk(io::Error),
+
     #[error("Failed to write to the disk: {0}")]
     WriteToDisk(io::Error),
 
     #[error("Failed to submit to the website: {0}")]
     WebsiteSubmit(Box<ureq::Error>),
+
+    #[error("Failed to use the cache: {0}")]
+    Cache(io::Error),
+
+    #[error("Failed to use the local answers: {0:#}")]
+    LocalAnswers(common::Error),
+
+    #[error("Submission refused, {0} (use --force to submit anyway)")]
+    SubmissionRefused(String),
 }
 
 #[derive(Debug, Parser)]
@@ -50,6 +87,15 @@ pub struct Cli {
     #[arg(short, long, default_value = "AOC_TOKEN")]
     token: String,
 
+    /// Directory of the inputs, where submitted and accepted answers are recorded.
+    /// Default: the `AOC_INPUTS` environment variable or the workspace "inputs" folder.
+    #[arg(long, value_name = "DIR")]
+    inputs: Option<PathBuf>,
+
+    /// Directory of downloaded pages. Default: "web-cache" in the workspace target folder.
+    #[arg(long, value_name = "DIR")]
+    cache: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -86,6 +132,22 @@ pub enum Commands {
         /// Save the puzzle input
         #[arg(short, long, value_name = "FILEPATH")]
         input: Option<PathBuf>,
+
+        /// Format of the saved puzzle description
+        #[arg(short, long, value_enum, default_value_t = Format::Html)]
+        format: Format,
+    },
+
+    /// Extract examples and their answers from the puzzle description
+    #[command(visible_alias = "ex")]
+    Examples {
+        /// Read the puzzle description from this saved webpage instead of downloading it
+        #[arg(short, long, value_name = "FILEPATH")]
+        page: Option<PathBuf>,
+
+        /// Write the examples to "example1.txt", ... in this directory instead of printing a snippet
+        #[arg(short, long, value_name = "DIR")]
+        write: Option<PathBuf>,
     },
 
     /// Submit an answer
@@ -96,6 +158,10 @@ pub enum Commands {
 
         /// Puzzle answer
         answer: String,
+
+        /// Submit even if previous attempts show the answer is wrong
+        #[arg(short, long)]
+        force: bool,
     },
 }
 
@@ -103,10 +169,11 @@ pub enum Commands {
 struct AocAgent {
     agent: ureq::Agent,
     cookie: String,
+    cache: Cache,
 }
 
 impl AocAgent {
-    fn from_token(token: &str) -> Result<Self> {
+    fn from_token(token: &str, cache_dir: &Path) -> Result<Self> {
         let tls = native_tls::TlsConnector::new().map_err(Error::NewTlsConnector)?;
         let agent = ureq::AgentBuilder::new()
             .https_only(true)
@@ -118,25 +185,32 @@ impl AocAgent {
             return Err(Error::InvalidSessionCookie(token.to_owned()));
         }
         let cookie = format!("session={token}");
-        Ok(Self { agent, cookie })
+        Ok(Self {
+            agent,
+            cache: Cache::new(cache_dir, &cookie),
+            cookie,
+        })
     }
 
-    fn from_env(env: &str) -> Result<Self> {
+    fn from_env(env: &str, cache_dir: &Path) -> Result<Self> {
         env::var(env)
             .map_err(|_| Error::InvalidSessionCookie(env.to_owned()))
-            .and_then(|token| Self::from_token(&token))
+            .and_then(|token| Self::from_token(&token, cache_dir))
     }
 
-    fn from_file<P: AsRef<Path>>(filepath: P) -> Result<Self> {
+    fn from_file<P: AsRef<Path>>(filepath: P, cache_dir: &Path) -> Result<Self> {
         std::fs::read_to_string(&filepath)
             .map_err(|_| Error::InvalidSessionCookie(filepath.as_ref().display().to_string()))
-            .and_then(|token| Self::from_token(&token))
+            .and_then(|token| Self::from_token(&token, cache_dir))
     }
 
-    fn download_url(&self, url: &str) -> Result<String> {
-        // Basic way to be nice with the server. Too basic?!
-        std::thread::sleep(Duration::from_secs(2));
-        self.agent
+    fn download_url(&self, url: &str, freshness: Freshness) -> Result<String> {
+        if let Some(text) = self.cache.get(url, freshness).map_err(Error::Cache)? {
+            return Ok(text);
+        }
+        self.cache.throttle().map_err(Error::Cache)?;
+        let text = self
+            .agent
             .get(url)
             .set("content-type", "text/plain")
             .set("cookie", &self.cookie)
@@ -145,10 +219,13 @@ impl AocAgent {
             .map_err(Box::new)
             .map_err(Error::WebsiteDownload)?
             .into_string()
-            .map_err(Error::WebsiteString)
+            .map_err(Error::WebsiteString)?;
+        self.cache.insert(url, &text).map_err(Error::Cache)?;
+        Ok(text)
     }
 
     fn submit_answer(&self, url: &str, part: Part, answer: &str) -> Result<String> {
+        self.cache.throttle().map_err(Error::Cache)?;
         self.agent
             .post(url)
             .set("content-type", "application/x-www-form-urlencoded")
@@ -171,6 +248,8 @@ impl Cli {
             year,
             day,
             token: token.unwrap_or_else(|| "AOC_TOKEN".to_owned()),
+            inputs: None,
+            cache: None,
             command,
         }
     }
@@ -203,14 +282,30 @@ impl Cli {
         format!("{WEBSITE}/{year}/day/{day}/answer")
     }
 
+    fn inputs_dir(&self) -> PathBuf {
+        self.inputs.clone().unwrap_or_else(|| {
+            env::var_os(INPUTS_DIR_VAR).map_or_else(
+                || Path::new(env!("CARGO_MANIFEST_DIR")).join("../inputs"),
+                PathBuf::from,
+            )
+        })
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.cache
+            .clone()
+            .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("../target/web-cache"))
+    }
+
     // TODO: Improve!
     fn aoc_agent(&self) -> Result<AocAgent> {
-        AocAgent::from_env(&self.token)
-            .or_else(|_| AocAgent::from_file(&self.token))
-            .or_else(|_| AocAgent::from_token(&self.token))
+        let cache_dir = self.cache_dir();
+        AocAgent::from_env(&self.token, &cache_dir)
+            .or_else(|_| AocAgent::from_file(&self.token, &cache_dir))
+            .or_else(|_| AocAgent::from_token(&self.token, &cache_dir))
     }
 
-    pub fn run(&self) -> Result<()> {
+    pub fn run(&self) -> Result<ExitCode> {
         match &self.command {
             &Commands::Open {
                 calendar,
@@ -231,18 +326,32 @@ impl Cli {
                 calendar,
                 description,
                 input,
+                format,
             } => {
                 let agent = self.aoc_agent()?;
-                for (filepath, url) in [
-                    (calendar, self.calendar_url()),
-                    (description, self.description_url()),
-                    (input, self.input_url()),
+                for (filepath, url, freshness, format) in [
+                    (
+                        calendar,
+                        self.calendar_url(),
+                        Freshness::For(CALENDAR_TTL),
+                        None,
+                    ),
+                    (
+                        description,
+                        self.description_url(),
+                        Freshness::UntilPart2(PART1_TTL),
+                        Some(*format),
+                    ),
+                    (input, self.input_url(), Freshness::Forever, None),
                 ] {
                     if let Some(filepath) = filepath {
                         if filepath.exists() {
                             eprintln!("This file already exists: {}", filepath.display());
                         } else {
-                            let text = agent.download_url(&url)?;
+                            let mut text = agent.download_url(&url, freshness)?;
+                            if let Some(format) = format {
+                                text = description::convert(&text, format);
+                            }
                             fs::OpenOptions::new()
                                 .create_new(true)
                                 .write(true)
@@ -254,13 +363,89 @@ impl Cli {
                     }
                 }
             }
-            Commands::Submit { part, answer } => {
-                let html = self
-                    .aoc_agent()?
-                    .submit_answer(&self.answer_url(), *part, answer)?;
-                println!("{html}");
+            Commands::Examples { page, write } => {
+                let page = match page {
+                    Some(filepath) => fs::read_to_string(filepath).map_err(Error::ReadFromDisk)?,
+                    None => self
+                        .aoc_agent()?
+                        .download_url(&self.description_url(), Freshness::UntilPart2(PART1_TTL))?,
+                };
+                let examples = examples::extract(&page);
+                if let Some(dir) = write {
+                    fs::create_dir_all(dir).map_err(Error::WriteToDisk)?;
+                    for (idx, example) in examples.iter().enumerate() {
+                        let filepath = dir.join(format!("example{}.txt", idx + 1));
+                        fs::write(&filepath, &example.input).map_err(Error::WriteToDisk)?;
+                        eprintln!("{} {:?}", filepath.display(), example.answers);
+                    }
+                } else {
+                    let answers = Answers::load(self.inputs_dir().join(ANSWERS_FILE))
+                        .map_err(Error::LocalAnswers)?;
+                    let big_answers =
+                        Part::ALL.map(|part| answers.get(self.year, self.day, part, MINE));
+                    print!(
+                        "{}",
+                        examples::snippet(&examples, self.year, self.day, big_answers)
+                    );
+                    let nb_without = examples
+                        .iter()
+                        .filter(|example| example.answers == [None, None])
+                        .count();
+                    if nb_without != 0 {
+                        eprintln!("{nb_without} code block(s) without answer, see --write");
+                    }
+                }
+            }
+            Commands::Submit {
+                part,
+                answer,
+                force,
+            } => {
+                let (year, day, part) = (self.year, self.day, *part);
+                let dir = self.inputs_dir();
+                let (ledger_path, answers_path) = (dir.join(LEDGER_FILE), dir.join(ANSWERS_FILE));
+                let mut ledger = Ledger::load(&ledger_path).map_err(Error::LocalAnswers)?;
+                let mut answers = Answers::load(&answers_path).map_err(Error::LocalAnswers)?;
+                if !force {
+                    let contradiction = answers
+                        .get(year, day, part, MINE)
+                        .filter(|known| known != answer)
+                        .map(|known| format!("the answer {known} is known to be correct"));
+                    if let Some(reason) = ledger.refusal(year, day, part, answer).or(contradiction)
+                    {
+                        return Err(Error::SubmissionRefused(reason));
+                    }
+                }
+                let agent = self.aoc_agent()?;
+                let html = agent.submit_answer(&self.answer_url(), part, answer)?;
+                let solved = ledger.correct(year, day, part).is_some()
+                    || answers.get(year, day, part, MINE).is_some();
+                let verdict = SubmitVerdict::parse(&html).knowing_solved(solved);
+                println!("{verdict}");
+                if let Some(outcome) = Outcome::from_verdict(&verdict) {
+                    fs::create_dir_all(&dir).map_err(Error::WriteToDisk)?;
+                    ledger
+                        .record(year, day, part, outcome, answer.clone())
+                        .and_then(|()| ledger.save(&ledger_path))
+                        .map_err(Error::LocalAnswers)?;
+                    if outcome == Outcome::Correct {
+                        // The next part got unlocked.
+                        agent
+                            .cache
+                            .remove(&self.description_url())
+                            .map_err(Error::Cache)?;
+                        let previous = answers
+                            .insert(year, day, part, MINE, answer.clone())
+                            .map_err(Error::LocalAnswers)?;
+                        if let Some(previous) = previous.filter(|previous| previous != answer) {
+                            eprintln!("The previously known answer was {previous}");
+                        }
+                        answers.save(&answers_path).map_err(Error::LocalAnswers)?;
+                    }
+                }
+                return Ok((&verdict).into());
             }
         }
-        Ok(())
+        Ok(ExitCode::SUCCESS)
     }
 }
@@ -1,7 +1,9 @@
+use std::process::ExitCode;
+
 use clap::Parser;
 
 use web::{Cli, Result};
 
-fn main() -> Result<()> {
+fn main() -> Result<ExitCode> {
     Cli::parse().run()
 }
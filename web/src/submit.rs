@@ -0,0 +1,217 @@
+use std::fmt;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use crate::html;
+
+/// Hint given along a wrong answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// What the website thinks of a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmitVerdict {
+    Correct,
+    Wrong {
+        hint: Option<Hint>,
+        /// How long to wait before trying again.
+        wait: Option<Duration>,
+    },
+    /// An answer was given too recently.
+    RateLimited {
+        wait: Option<Duration>,
+    },
+    /// Not the part to solve, and a correct answer to it is recorded.
+    AlreadySolved,
+    /// Not the part to solve: the first part is not solved, or this one was solved elsewhere.
+    WrongLevel,
+    /// The response was not understood, here is its text.
+    Unknown(String),
+}
+
+impl SubmitVerdict {
+    /// Parse the `<article>` of the response to a submission.
+    #[must_use]
+    pub fn parse(html: &str) -> Self {
+        let text = html::articles(html)
+            .first()
+            .map_or_else(|| html::text(html), |(_, article)| html::text(article));
+        if text.starts_with("That's the right answer") {
+            Self::Correct
+        } else if text.starts_with("That's not the right answer") {
+            let hint = if text.contains("too high") {
+                Some(Hint::TooHigh)
+            } else if text.contains("too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+            let wait = text
+                .split_once("wait ")
+                .and_then(|(_, rest)| rest.split_once(" before trying again"))
+                .and_then(|(duration, _)| parse_duration(duration));
+            Self::Wrong { hint, wait }
+        } else if text.starts_with("You gave an answer too recently") {
+            let wait = text
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .and_then(|(duration, _)| parse_duration(duration));
+            Self::RateLimited { wait }
+        } else if text.contains("You don't seem to be solving the right level") {
+            Self::WrongLevel
+        } else {
+            Self::Unknown(text)
+        }
+    }
+
+    /// The website gives the same response to an already solved part and to a locked one,
+    /// so it's told apart by whether a correct answer to the part is recorded.
+    #[must_use]
+    pub fn knowing_solved(self, solved: bool) -> Self {
+        match self {
+            Self::WrongLevel if solved => Self::AlreadySolved,
+            verdict => verdict,
+        }
+    }
+
+    /// Exit code of `web submit`, `1` being for errors and `2` for invalid arguments.
+    #[must_use]
+    pub const fn exit_code(&self) -> u8 {
+        match self {
+            Self::Correct => 0,
+            Self::Wrong { .. } => 3,
+            Self::RateLimited { .. } => 4,
+            Self::AlreadySolved => 5,
+            Self::WrongLevel => 6,
+            Self::Unknown(_) => 7,
+        }
+    }
+}
+
+impl From<&SubmitVerdict> for ExitCode {
+    fn from(verdict: &SubmitVerdict) -> Self {
+        Self::from(verdict.exit_code())
+    }
+}
+
+impl fmt::Display for SubmitVerdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "Correct answer!"),
+            Self::Wrong { hint, wait } => {
+                write!(f, "Wrong answer")?;
+                match hint {
+                    Some(Hint::TooHigh) => write!(f, ", too high")?,
+                    Some(Hint::TooLow) => write!(f, ", too low")?,
+                    None => {}
+                }
+                if let Some(wait) = wait {
+                    write!(f, ", wait {}s", wait.as_secs())?;
+                }
+                Ok(())
+            }
+            Self::RateLimited { wait: Some(wait) } => {
+                write!(f, "Answered too recently, wait {}s", wait.as_secs())
+            }
+            Self::RateLimited { wait: None } => write!(f, "Answered too recently"),
+            Self::AlreadySolved => write!(f, "Already solved"),
+            Self::WrongLevel => write!(f, "Wrong level"),
+            Self::Unknown(text) => write!(f, "Unknown response: {text}"),
+        }
+    }
+}
+
+/// Parse durations such as "one minute", "5 minutes", "34s" or "4m 12s".
+fn parse_duration(text: &str) -> Option<Duration> {
+    let mut secs = 0;
+    let mut words = text.split_whitespace().peekable();
+    let mut found = false;
+    while let Some(word) = words.next() {
+        let (number, unit) = match word {
+            "one" | "a" | "an" => (1, words.next()?),
+            _ => match word.find(|c: char| !c.is_ascii_digit()) {
+                Some(0) => continue,
+                Some(idx) => (word[..idx].parse().ok()?, &word[idx..]),
+                None => (word.parse().ok()?, words.next()?),
+            },
+        };
+        let unit_secs = match unit.trim_end_matches(['s', ',', '.']) {
+            "" | "second" => 1,
+            "m" | "minute" => 60,
+            "h" | "hour" => 3600,
+            _ => return None,
+        };
+        secs += number * unit_secs;
+        found = true;
+    }
+    found.then(|| Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_verdicts() {
+        let minutes = |m: u64| Some(Duration::from_secs(60 * m));
+        for (html, expected) in [
+            (
+                include_str!("../fixtures/submit/correct.html"),
+                SubmitVerdict::Correct,
+            ),
+            (
+                include_str!("../fixtures/submit/too_high.html"),
+                SubmitVerdict::Wrong {
+                    hint: Some(Hint::TooHigh),
+                    wait: minutes(1),
+                },
+            ),
+            (
+                include_str!("../fixtures/submit/too_low.html"),
+                SubmitVerdict::Wrong {
+                    hint: Some(Hint::TooLow),
+                    wait: minutes(5),
+                },
+            ),
+            (
+                include_str!("../fixtures/submit/wrong.html"),
+                SubmitVerdict::Wrong {
+                    hint: None,
+                    wait: minutes(1),
+                },
+            ),
+            (
+                include_str!("../fixtures/submit/rate_limited.html"),
+                SubmitVerdict::RateLimited {
+                    wait: Some(Duration::from_secs(4 * 60 + 12)),
+                },
+            ),
+            (
+                include_str!("../fixtures/submit/wrong_level.html"),
+                SubmitVerdict::WrongLevel,
+            ),
+        ] {
+            assert_eq!(SubmitVerdict::parse(html), expected);
+        }
+        let wrong_level = SubmitVerdict::parse(include_str!("../fixtures/submit/wrong_level.html"));
+        assert_eq!(
+            wrong_level.clone().knowing_solved(true),
+            SubmitVerdict::AlreadySolved
+        );
+        assert_eq!(wrong_level.knowing_solved(false), SubmitVerdict::WrongLevel);
+        assert_eq!(
+            SubmitVerdict::Correct.knowing_solved(true),
+            SubmitVerdict::Correct
+        );
+        assert!(matches!(
+            SubmitVerdict::parse("<article><p>Hello</p></article>"),
+            SubmitVerdict::Unknown(text) if text == "Hello",
+        ));
+        assert_eq!(parse_duration("34s"), Some(Duration::from_secs(34)));
+        assert_eq!(parse_duration("an hour"), Some(Duration::from_secs(3600)));
+        assert_eq!(parse_duration("soon"), None);
+    }
+}